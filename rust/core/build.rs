@@ -0,0 +1,209 @@
+//! Turns biomes.json into Rust at build time so the wasm module doesn't have to parse it and
+//! search every path on startup. The output is included by `src/precomputed.rs`.
+
+use serde_json::Value;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const BIOMES_JSON: &str = "src/json/biomes.json";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", BIOMES_JSON);
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let json = fs::read_to_string(BIOMES_JSON).expect("read biomes.json");
+    let biomes: Vec<Value> = serde_json::from_str(&json).expect("parse biomes.json");
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// Generated by build.rs from {}, don't edit",
+        BIOMES_JSON
+    )
+    .unwrap();
+    write_biomes(&mut out, &biomes);
+    let edges = edges(&biomes);
+    write_graph(&mut out, &biomes, &edges);
+    write_paths(&mut out, &biomes, &edges);
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("biomes.rs"), out).expect("write biomes.rs");
+}
+
+fn id(biome: &Value) -> &str {
+    biome["id"].as_str().expect("biome id")
+}
+
+/// A required numeric field, biomes.json is compiled in so a bad one should fail the build
+fn number(biome: &Value, field: &str) -> u64 {
+    biome[field]
+        .as_u64()
+        .unwrap_or_else(|| panic!("biome {} has no numeric {}", id(biome), field))
+}
+
+fn option(value: &Value) -> String {
+    match value.as_u64() {
+        Some(number) => format!("Some({})", number),
+        None => "None".to_string(),
+    }
+}
+
+fn write_biomes(out: &mut String, biomes: &[Value]) {
+    writeln!(out, "pub(crate) fn biomes() -> Vec<Biome> {{\n    vec![").unwrap();
+    for biome in biomes {
+        let fragments = &biome["scroll_fragments"];
+        let fragment =
+            |name: &str, alias: &str| option(fragments.get(name).unwrap_or(&fragments[alias]));
+        writeln!(
+            out,
+            "        Biome {{
+            id: Id::new({:?}),
+            name: {:?}.to_string(),
+            row: {},
+            column: {},
+            power_scrolls: {},
+            dual_power_scrolls: {},
+            cursed_chest_chance: {},
+            scroll_fragments: ScrollFragments {{
+                zero: {},
+                one: {},
+                two: {},
+                three: {},
+                four: {},
+                five: {},
+            }},
+            gear_level: {},
+            exits: vec![",
+            id(biome),
+            biome["name"].as_str().expect("biome name"),
+            number(biome, "row"),
+            number(biome, "column"),
+            number(biome, "power_scrolls"),
+            number(biome, "dual_power_scrolls"),
+            number(biome, "cursed_chest_chance"),
+            fragment("zero", "0"),
+            fragment("one", "1"),
+            fragment("two", "2"),
+            fragment("three", "3"),
+            fragment("four", "4"),
+            fragment("five", "5"),
+            number(biome, "gear_level"),
+        )
+        .unwrap();
+        for exit in exits(biome) {
+            writeln!(
+                out,
+                "                Exit {{
+                    destination: Id::new({:?}),
+                    boss_cell_requirement: {},
+                    power_scrolls: {},
+                    requirements: vec![{}],
+                }},",
+                exit["destination"].as_str().expect("exit destination"),
+                option(&exit["boss_cell_requirement"]),
+                option(&exit["power_scrolls"]),
+                requirements(exit).join(", "),
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "            ],
+            enabled: false,
+            disabled_reasons: vec![],
+        }},"
+        )
+        .unwrap();
+    }
+    writeln!(out, "    ]\n}}").unwrap();
+}
+
+fn exits(biome: &Value) -> &[Value] {
+    biome["exits"].as_array().map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn requirements(exit: &Value) -> Vec<String> {
+    let requirements = exit["requirements"].as_array().map(Vec::as_slice);
+    requirements
+        .unwrap_or(&[])
+        .iter()
+        .map(|requirement| {
+            if let Some(boss_cells) = requirement["boss_cells"].as_u64() {
+                format!("Requirement::BossCells({})", boss_cells)
+            } else if let Some(rune) = requirement["rune"].as_str() {
+                let mut variant = rune.to_string();
+                variant[..1].make_ascii_uppercase();
+                format!("Requirement::Rune(Rune::{})", variant)
+            } else if let Some(key) = requirement["key"].as_str() {
+                format!("Requirement::Key({:?}.to_string())", key)
+            } else {
+                panic!("unknown requirement {}", requirement)
+            }
+        })
+        .collect()
+}
+
+/// The destination index of every exit, per biome
+fn edges(biomes: &[Value]) -> Vec<Vec<usize>> {
+    biomes
+        .iter()
+        .map(|biome| {
+            exits(biome)
+                .iter()
+                .map(|exit| {
+                    let destination = exit["destination"].as_str();
+                    biomes
+                        .iter()
+                        .position(|other| other["id"].as_str() == destination)
+                        .unwrap_or_else(|| panic!("no biome with id {:?}", destination))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn write_graph(out: &mut String, biomes: &[Value], edges: &[Vec<usize>]) {
+    let mut order: Vec<usize> = (0..biomes.len()).collect();
+    order.sort_by_key(|i| number(&biomes[*i], "row"));
+    writeln!(out, "pub(crate) static ORDER: &[usize] = &{:?};", order).unwrap();
+    writeln!(out, "pub(crate) static EDGES: &[&[usize]] = &[").unwrap();
+    for destinations in edges {
+        writeln!(out, "    &{:?},", destinations).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+}
+
+/// Every path from the first biome to any biome a run can end in, in the order
+/// `path::find_paths_between` finds them
+fn write_paths(out: &mut String, biomes: &[Value], edges: &[Vec<usize>]) {
+    let ends: Vec<usize> = (0..biomes.len())
+        .filter(|i| id(&biomes[*i]) == "throne" || edges[*i].is_empty())
+        .collect();
+    let mut paths = vec![];
+    if !biomes.is_empty() {
+        find_paths(edges, &ends, vec![0], &mut paths);
+    }
+    writeln!(out, "pub(crate) static PATHS: &[&[usize]] = &[").unwrap();
+    for path in paths {
+        writeln!(out, "    &{:?},", path).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+}
+
+fn find_paths(edges: &[Vec<usize>], ends: &[usize], path: Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+    let last = *path.last().expect("paths are never empty");
+    if ends.contains(&last) {
+        paths.push(path.clone());
+    }
+    // biomes are visited in dataset order, not exit order
+    let mut next: Vec<usize> = edges[last].clone();
+    next.sort_unstable();
+    next.dedup();
+    for biome in next {
+        let mut next_path = path.clone();
+        next_path.push(biome);
+        find_paths(edges, ends, next_path, paths);
+    }
+}
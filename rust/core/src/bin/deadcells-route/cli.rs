@@ -0,0 +1,445 @@
+//! The `deadcells-route` command, the same planner as the web page without a browser
+
+use deadcells_route_core::blacklist::Blacklist;
+use deadcells_route_core::collectibles::BiomeExplanation;
+use deadcells_route_core::constraints::Constraints;
+use deadcells_route_core::core;
+use deadcells_route_core::core::RankedRoute;
+use deadcells_route_core::error::Error;
+use deadcells_route_core::json::models::*;
+use deadcells_route_core::json::validation::{self, ValidationError};
+use deadcells_route_core::scoring::{RouteTotals, ScrollScorer};
+use serde::Serialize;
+use std::fs;
+
+const USAGE: &str = "Usage: deadcells-route <command> [options]
+
+Commands:
+  biomes                   list the biomes row by row, and why the ones that are disabled are
+  best                     show the route with the most scrolls
+  top                      show the routes with the most scrolls, best first
+  explain <biome>...       show what every biome of the route adds
+  validate [file]          check a dataset, the compiled-in one if no file is given
+
+Options:
+  --boss-cells <n>         boss cells, 0 to 5, 0 if left out
+  --blacklist <ids>        comma separated biome ids and exit ids like toxicsewers-ramparts
+  --top <k>                how many routes top shows, 5 if left out
+  --dual                   count dual scrolls as well
+  --dataset <file>         a biomes.json to use instead of the compiled-in one
+  --json                   print JSON instead of text";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Biomes,
+    Best,
+    Top,
+    Explain(Vec<Id>),
+    Validate,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    boss_cells: u8,
+    blacklist: Blacklist,
+    top: usize,
+    include_dual_scrolls: bool,
+    dataset: Option<String>,
+    json: bool,
+}
+
+/// What a command prints, `success` is false when it found problems, like `validate` does in an
+/// invalid dataset
+#[derive(Debug, PartialEq)]
+pub struct Output {
+    pub text: String,
+    pub success: bool,
+}
+
+impl From<String> for Output {
+    fn from(text: String) -> Self {
+        Output {
+            text,
+            success: true,
+        }
+    }
+}
+
+/// Runs the command and prints what it has to say, returns the exit code
+pub fn main(args: &[String]) -> i32 {
+    let json = args.iter().any(|arg| arg == "--json");
+    match run(args) {
+        Ok(output) => {
+            println!("{}", output.text);
+            if output.success {
+                0
+            } else {
+                1
+            }
+        }
+        Err(error) if json => {
+            println!("{}", to_json(&error).unwrap_or_else(|_| error.to_string()));
+            1
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            1
+        }
+    }
+}
+
+/// The output of the command, `args` don't include the program name
+pub fn run(args: &[String]) -> Result<Output, Error> {
+    let options = parse(args)?;
+    let dataset = match &options.dataset {
+        Some(file) => Some(read_dataset(file)?),
+        None => None,
+    };
+    let progress: Progress = options.boss_cells.into();
+    let constraints = Constraints::default();
+    let scorer = ScrollScorer {
+        include_dual_scrolls: options.include_dual_scrolls,
+    };
+
+    let json = options.json;
+    match &options.command {
+        Command::Help => Ok(USAGE.to_string().into()),
+        Command::Biomes => {
+            let (tiers, _) =
+                core::get_biomes_and_paths(options.blacklist, &progress, &constraints, dataset)?;
+            output(json, &tiers, |tiers| show_biomes(tiers))
+        }
+        Command::Best => {
+            let route =
+                core::get_best_route(options.blacklist, &progress, &constraints, &scorer, dataset)?;
+            output(json, &route, |route| {
+                show_routes(std::slice::from_ref(route))
+            })
+        }
+        Command::Top => {
+            let routes = core::get_top_routes(
+                options.blacklist,
+                &progress,
+                &constraints,
+                &scorer,
+                options.top,
+                dataset,
+            )?;
+            output(json, &routes, |routes| show_routes(routes))
+        }
+        Command::Explain(route) => {
+            let explanation = core::explain_route(route, options.boss_cells, dataset)?;
+            output(json, &explanation, |explanation| {
+                show_explanation(explanation)
+            })
+        }
+        Command::Validate => {
+            let biomes = match dataset {
+                Some(biomes) => biomes,
+                None => core::get_biomes()?,
+            };
+            let errors = validation::validate(&biomes);
+            Ok(Output {
+                success: errors.is_empty(),
+                ..output(json, &errors, |errors| show_problems(&biomes, errors))?
+            })
+        }
+    }
+}
+
+/// `value` as JSON, or as text made by `text`
+fn output<T: Serialize>(
+    json: bool,
+    value: &T,
+    text: impl FnOnce(&T) -> String,
+) -> Result<Output, Error> {
+    if json {
+        Ok(to_json(value)?.into())
+    } else {
+        Ok(text(value).into())
+    }
+}
+
+fn parse(args: &[String]) -> Result<Options, Error> {
+    let invalid = |message: String| Error::InvalidInput(format!("{}\n\n{}", message, USAGE));
+    let mut options = Options {
+        command: Command::Help,
+        boss_cells: 0,
+        blacklist: Blacklist::default(),
+        top: 5,
+        include_dual_scrolls: false,
+        dataset: None,
+        json: false,
+    };
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| invalid(format!("{} needs a value", name)))
+        };
+        match arg.as_str() {
+            "--boss-cells" => {
+                options.boss_cells = match value(arg)?.parse() {
+                    Ok(boss_cells) if boss_cells <= 5 => boss_cells,
+                    _ => return Err(invalid("--boss-cells is a number from 0 to 5".to_string())),
+                }
+            }
+            "--blacklist" => {
+                options.blacklist =
+                    Blacklist::from_entries(value(arg)?.split(',').filter(|id| !id.is_empty()))
+            }
+            "--top" => {
+                options.top = value(arg)?
+                    .parse()
+                    .map_err(|_| invalid("--top is a number".to_string()))?
+            }
+            "--dataset" => options.dataset = Some(value(arg)?.clone()),
+            "--dual" => options.include_dual_scrolls = true,
+            "--json" => options.json = true,
+            "-h" | "--help" => {
+                options.command = Command::Help;
+                return Ok(options);
+            }
+            flag if flag.starts_with("--") => {
+                return Err(invalid(format!("Unknown option {}", flag)))
+            }
+            positional_arg => positional.push(positional_arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    options.command = match positional.next() {
+        None | Some("help") => Command::Help,
+        Some("biomes") => Command::Biomes,
+        Some("best") => Command::Best,
+        Some("top") => Command::Top,
+        Some("explain") => Command::Explain(positional.by_ref().map(Id::new).collect()),
+        Some("validate") => {
+            if let Some(file) = positional.next() {
+                options.dataset = Some(file.to_string());
+            }
+            Command::Validate
+        }
+        Some(command) => return Err(invalid(format!("Unknown command {}", command))),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(invalid(format!("Unexpected argument {}", extra)));
+    }
+    Ok(options)
+}
+
+fn read_dataset(file: &str) -> Result<Vec<Biome>, Error> {
+    let json = fs::read_to_string(file)
+        .map_err(|err| Error::InvalidInput(format!("Can't read {}: {}", file, err)))?;
+    core::get_biomes_from_str(&json)
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, Error> {
+    serde_json::to_string_pretty(value)
+        .map_err(|err| Error::Internal(format!("Failed to serialize: {}", err)))
+}
+
+fn show_biomes(tiers: &[Vec<Biome>]) -> String {
+    let mut lines = vec![];
+    for biome in tiers.iter().flatten() {
+        let mut line = format!(
+            "{:>2}  {:<16} {}",
+            biome.row,
+            biome.id.to_string(),
+            biome.name
+        );
+        let reasons: Vec<String> = biome
+            .disabled_reasons
+            .iter()
+            .map(|reason| reason.to_string())
+            .collect();
+        if !reasons.is_empty() {
+            line.push_str(&format!(" ({})", reasons.join(", ")));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn show_problems(biomes: &[Biome], errors: &[ValidationError]) -> String {
+    if errors.is_empty() {
+        return format!("{} biomes, no problems", biomes.len());
+    }
+    errors
+        .iter()
+        .map(|error| error.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn show_routes(routes: &[RankedRoute]) -> String {
+    routes
+        .iter()
+        .enumerate()
+        .map(|(i, route)| {
+            let ids: Vec<String> = route.route.iter().map(|id| id.to_string()).collect();
+            format!(
+                "{}. {} (score {:.2})\n   {}",
+                i + 1,
+                ids.join(" > "),
+                route.score,
+                show_totals(&route.collectibles.totals)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn show_explanation(explanation: &[BiomeExplanation]) -> String {
+    explanation
+        .iter()
+        .map(|step| {
+            let item = &step.line_item;
+            let conversion = if step.converts_fragments {
+                ", fragments make a scroll"
+            } else {
+                ""
+            };
+            format!(
+                "{:<16} +{} scrolls, +{} dual, +{} fragments, +{}% cursed chests{}\n{:<16} {}",
+                item.biome.to_string(),
+                item.power_scrolls + item.transition_scrolls,
+                item.dual_power_scrolls,
+                item.scroll_fragments,
+                item.cursed_chest_chance,
+                conversion,
+                "",
+                show_totals(&step.running_totals),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn show_totals(totals: &RouteTotals) -> String {
+    format!(
+        "{} scrolls, {} dual, {} fragments, {}% cursed chests, gear level {}",
+        totals.power_scrolls + totals.transition_scrolls,
+        totals.dual_power_scrolls,
+        totals.scroll_fragments,
+        totals.cursed_chest_chance,
+        totals.gear_level
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn should_parse_options() {
+        let options = parse(&args(
+            "top --boss-cells 3 --blacklist ossuary,toxicsewers-ramparts --top 2 --json",
+        ))
+        .unwrap();
+
+        assert_eq!(options.command, Command::Top);
+        assert_eq!(options.boss_cells, 3);
+        assert_eq!(
+            options.blacklist,
+            Blacklist::from_entries(vec!["ossuary", "toxicsewers-ramparts"])
+        );
+        assert_eq!(options.top, 2);
+        assert!(options.json);
+        assert_eq!(
+            parse(&args("explain prisonquart promenade"))
+                .unwrap()
+                .command,
+            Command::Explain(vec![Id::Prisonquart, Id::Promenade])
+        );
+        assert_eq!(parse(&args("best --help")).unwrap().command, Command::Help);
+        assert!(parse(&args("best --boss-cells 6")).is_err());
+        assert!(parse(&args("fly")).is_err());
+        assert!(parse(&args("best --top")).is_err());
+    }
+
+    #[test]
+    fn should_print_the_same_routes_as_the_planner() {
+        let json = run(&args("top --boss-cells 5 --top 3 --json"))
+            .unwrap()
+            .text;
+        let routes: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let expected = core::get_top_routes(
+            Blacklist::default(),
+            &5.into(),
+            &Constraints::default(),
+            &ScrollScorer::default(),
+            3,
+            None,
+        )
+        .unwrap();
+
+        let ids = |routes: &serde_json::Value| -> Vec<serde_json::Value> {
+            routes
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|route| route["route"].clone())
+                .collect()
+        };
+        assert_eq!(ids(&routes), ids(&serde_json::to_value(&expected).unwrap()));
+        let text = run(&args("best --boss-cells 5")).unwrap().text;
+        assert!(text.starts_with("1. prisonquart > "));
+    }
+
+    #[test]
+    fn should_fail_on_routes_and_datasets_that_dont_work() {
+        assert_eq!(
+            run(&args("explain prisonquart ossuary")),
+            Err(Error::InvalidInput(
+                "No exit from prisonquart to ossuary".to_string()
+            ))
+        );
+        assert!(matches!(
+            run(&args("validate does-not-exist.json")),
+            Err(Error::InvalidInput(_))
+        ));
+        assert_eq!(
+            run(&args("validate")),
+            Ok(Output {
+                text: format!("{} biomes, no problems", core::get_biomes().unwrap().len()),
+                success: true
+            })
+        );
+    }
+
+    #[test]
+    fn should_list_every_problem_in_a_dataset() {
+        let file = std::env::temp_dir().join("deadcells-route-invalid-biomes.json");
+        fs::write(
+            &file,
+            r#"[{"id": "prisonquart", "name": "Prisoners' Quarters", "row": 0, "column": 1,
+                 "power_scrolls": 0, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+                 "scroll_fragments": {}, "gear_level": 1, "exits": []}]"#,
+        )
+        .unwrap();
+        let file = file.to_str().unwrap();
+
+        let output = run(&args(&format!("validate {} --json", file))).unwrap();
+        let errors: serde_json::Value = serde_json::from_str(&output.text).unwrap();
+        assert!(!output.success);
+        assert_eq!(
+            errors,
+            serde_json::json!([
+                {"biome": "prisonquart", "field": "row", "message": "row 0 is outside of 1..=14"},
+                {"biome": "throne", "field": "id", "message": "the dataset has no throne room"},
+            ])
+        );
+        assert_eq!(
+            run(&args(&format!("validate {}", file))).unwrap().text,
+            "prisonquart.row: row 0 is outside of 1..=14\nthrone.id: the dataset has no throne room"
+        );
+    }
+}
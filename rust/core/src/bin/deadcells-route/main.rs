@@ -0,0 +1,9 @@
+mod cli;
+
+use std::env;
+use std::process;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    process::exit(cli::main(&args));
+}
@@ -0,0 +1,83 @@
+use crate::constraints::ExitId;
+use crate::json::models::*;
+
+/// The biomes and exits routes stay away from. Exits are written like `RenderablePath.id`, so
+/// `"toxicsewers-ramparts"` still allows Ramparts, just never coming from the Toxic Sewers.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct Blacklist {
+    pub biomes: Vec<Id>,
+    pub exits: Vec<ExitId>,
+}
+
+impl From<Vec<Id>> for Blacklist {
+    fn from(biomes: Vec<Id>) -> Self {
+        Blacklist {
+            biomes,
+            exits: vec![],
+        }
+    }
+}
+
+impl Blacklist {
+    /// Biome ids and `from-to` exit ids, `validation::validate` makes sure biome ids never have a
+    /// dash in them
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut blacklist = Blacklist::default();
+        for entry in entries {
+            match entry.find('-') {
+                Some(dash) => blacklist.exits.push(ExitId {
+                    from: Id::new(&entry[..dash]),
+                    to: Id::new(&entry[dash + 1..]),
+                }),
+                None => blacklist.biomes.push(Id::new(entry)),
+            }
+        }
+        blacklist
+    }
+
+    pub fn contains_biome(&self, id: &Id) -> bool {
+        self.biomes.contains(id)
+    }
+
+    pub fn contains_exit(&self, from: &Id, to: &Id) -> bool {
+        self.exits
+            .iter()
+            .any(|exit| &exit.from == from && &exit.to == to)
+    }
+
+    /// Whether a route can go from `from` to `to`, which takes neither the exit nor `to` to be
+    /// blacklisted
+    pub fn allows_exit(&self, from: &Id, to: &Id) -> bool {
+        !self.contains_biome(to) && !self.contains_exit(from, to)
+    }
+
+    /// Whether the path goes through a blacklisted biome or exit
+    pub fn blocks(&self, path: &[&Biome]) -> bool {
+        path.iter().any(|biome| self.contains_biome(&biome.id))
+            || path
+                .windows(2)
+                .any(|pair| self.contains_exit(&pair[0].id, &pair[1].id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_tell_biomes_and_exits_apart() {
+        let blacklist = Blacklist::from_entries(vec!["ossuary", "toxicsewers-ramparts"]);
+
+        assert_eq!(blacklist.biomes, vec![Id::Ossuary]);
+        assert_eq!(
+            blacklist.exits,
+            vec![ExitId {
+                from: Id::Toxicsewers,
+                to: Id::Ramparts
+            }]
+        );
+        assert!(!blacklist.allows_exit(&Id::Toxicsewers, &Id::Ramparts));
+        assert!(blacklist.allows_exit(&Id::Promenade, &Id::Ramparts));
+        assert!(!blacklist.allows_exit(&Id::Promenade, &Id::Ossuary));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::json::models::*;
+use serde::Serialize;
+
+/// The cursed chests a route runs into. A biome's `cursed_chest_chance` is the expected number
+/// of cursed chests in it in percent, so 150 is one chest for sure and a 50% chance of another.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct CursedChests {
+    /// expected number of cursed chests, every one of them gives a scroll
+    pub expected: f64,
+    /// `at_least[n]` is the probability of running into at least `n` cursed chests
+    pub at_least: Vec<f64>,
+}
+
+impl CursedChests {
+    pub fn of(chances: impl IntoIterator<Item = u8>) -> Self {
+        // probability of running into exactly as many chests as the index
+        let mut exactly = vec![1.];
+        let mut expected = 0.;
+        for chance in chances {
+            let certain = (chance / 100) as usize;
+            let maybe = (chance % 100) as f64 / 100.;
+            expected += chance as f64 / 100.;
+
+            let mut next = vec![0.; exactly.len() + certain + 1];
+            for (chests, probability) in exactly.iter().enumerate() {
+                next[chests + certain] += probability * (1. - maybe);
+                next[chests + certain + 1] += probability * maybe;
+            }
+            while next.len() > 1 && next[next.len() - 1] == 0. {
+                next.pop();
+            }
+            exactly = next;
+        }
+
+        let mut at_least = exactly;
+        for chests in (0..at_least.len() - 1).rev() {
+            at_least[chests] += at_least[chests + 1];
+        }
+        CursedChests { expected, at_least }
+    }
+
+    pub fn for_path(path: &[&Biome]) -> Self {
+        CursedChests::of(path.iter().map(|biome| biome.cursed_chest_chance))
+    }
+
+    pub fn probability_of_at_least(&self, chests: usize) -> f64 {
+        self.at_least.get(chests).cloned().unwrap_or(0.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn should_treat_chances_as_expected_chests() {
+        let chests = CursedChests::of(vec![150, 50, 0]);
+
+        assert_close(chests.expected, 2.);
+        assert_eq!(chests.at_least.len(), 4);
+        assert_close(chests.probability_of_at_least(0), 1.);
+        assert_close(chests.probability_of_at_least(1), 1.);
+        assert_close(chests.probability_of_at_least(2), 0.75);
+        assert_close(chests.probability_of_at_least(3), 0.25);
+        assert_close(chests.probability_of_at_least(4), 0.);
+    }
+
+    #[test]
+    fn a_route_without_cursed_chests_should_meet_none() {
+        let chests = CursedChests::of(vec![0, 0]);
+
+        assert_close(chests.expected, 0.);
+        assert_eq!(chests.at_least, vec![1.]);
+    }
+}
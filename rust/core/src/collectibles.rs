@@ -0,0 +1,185 @@
+use crate::json::models::*;
+use crate::scoring::RouteTotals;
+use serde::Serialize;
+
+/// What a route picks up, in total and biome by biome
+#[derive(Serialize, Debug, Default, Eq, PartialEq, Clone)]
+pub struct Collectibles {
+    #[serde(flatten)]
+    pub totals: RouteTotals,
+    /// scrolls made out of 4 fragments each
+    pub fragment_conversions: u32,
+    /// fragments that don't add up to another scroll
+    pub leftover_fragments: u32,
+    /// one per biome, in route order
+    pub line_items: Vec<LineItem>,
+}
+
+/// What a single biome adds to the route
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct LineItem {
+    pub biome: Id,
+    pub power_scrolls: u32,
+    pub dual_power_scrolls: u32,
+    pub scroll_fragments: u32,
+    /// scrolls the route's fragments turn into once the ones in this biome are picked up
+    pub fragment_conversions: u32,
+    pub cursed_chest_chance: u32,
+    /// scrolls for taking the exit into this biome
+    pub transition_scrolls: u32,
+    pub gear_level: u32,
+}
+
+impl LineItem {
+    fn between(biome: &Biome, before: &RouteTotals, after: &RouteTotals) -> Self {
+        LineItem {
+            biome: biome.id.clone(),
+            power_scrolls: after.power_scrolls - before.power_scrolls,
+            dual_power_scrolls: after.dual_power_scrolls - before.dual_power_scrolls,
+            scroll_fragments: after.scroll_fragments - before.scroll_fragments,
+            fragment_conversions: after.scrolls_from_fragments() - before.scrolls_from_fragments(),
+            cursed_chest_chance: after.cursed_chest_chance - before.cursed_chest_chance,
+            transition_scrolls: after.transition_scrolls - before.transition_scrolls,
+            gear_level: after.gear_level - before.gear_level,
+        }
+    }
+}
+
+/// A biome of a route, what it adds and where the route stands after it
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct BiomeExplanation {
+    #[serde(flatten)]
+    pub line_item: LineItem,
+    /// everything picked up so far, this biome included
+    pub running_totals: RouteTotals,
+    /// whether fragments turn into scrolls in this biome
+    pub converts_fragments: bool,
+}
+
+impl Collectibles {
+    pub fn for_path(path: &[&Biome], boss_cells: u8) -> Self {
+        let mut line_items = Vec::with_capacity(path.len());
+        let totals = walk(path, boss_cells, |line_item, _| line_items.push(line_item));
+
+        Collectibles {
+            totals,
+            fragment_conversions: totals.scrolls_from_fragments(),
+            leftover_fragments: totals.scroll_fragments % 4,
+            line_items,
+        }
+    }
+}
+
+/// Goes through the route biome by biome, see `BiomeExplanation`
+pub fn explain(path: &[&Biome], boss_cells: u8) -> Vec<BiomeExplanation> {
+    let mut explanations = Vec::with_capacity(path.len());
+    walk(path, boss_cells, |line_item, running_totals| {
+        explanations.push(BiomeExplanation {
+            converts_fragments: line_item.fragment_conversions > 0,
+            line_item,
+            running_totals: *running_totals,
+        })
+    });
+    explanations
+}
+
+/// Adds up the route, handing every biome's line item and the totals after it to `f`
+fn walk(path: &[&Biome], boss_cells: u8, mut f: impl FnMut(LineItem, &RouteTotals)) -> RouteTotals {
+    let mut before = RouteTotals::default();
+    for (biome, totals) in RouteTotals::running(path, boss_cells) {
+        f(LineItem::between(biome, &before, &totals), &totals);
+        before = totals;
+    }
+    before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+
+    #[test]
+    fn line_items_should_add_up_to_the_totals() {
+        let biomes = core::get_biomes().unwrap();
+        let path: Vec<&Biome> = ["prisonquart", "arboretum", "prisondepths", "ancientsewers"]
+            .iter()
+            .map(|id| biomes.iter().find(|biome| biome.id == Id::new(id)).unwrap())
+            .collect();
+
+        let collectibles = Collectibles::for_path(&path, 5);
+
+        assert_eq!(collectibles.totals, RouteTotals::of(&path, 5));
+        assert_eq!(collectibles.line_items.len(), path.len());
+        let items = &collectibles.line_items;
+        let sum = |field: fn(&LineItem) -> u32| items.iter().map(field).sum::<u32>();
+        assert_eq!(
+            sum(|item| item.power_scrolls),
+            collectibles.totals.power_scrolls
+        );
+        assert_eq!(
+            sum(|item| item.scroll_fragments),
+            collectibles.totals.scroll_fragments
+        );
+        assert_eq!(
+            sum(|item| item.fragment_conversions),
+            collectibles.fragment_conversions
+        );
+        assert_eq!(
+            collectibles.fragment_conversions * 4 + collectibles.leftover_fragments,
+            collectibles.totals.scroll_fragments
+        );
+    }
+
+    #[test]
+    fn should_convert_fragments_where_they_add_up_to_4() {
+        let biome = |id: Id, fragments: u8| Biome {
+            scroll_fragments: ScrollFragments {
+                zero: Some(fragments),
+                ..ScrollFragments::default()
+            },
+            ..(id, 1, 1).into()
+        };
+        let biomes = [
+            biome(Id::Prisonquart, 3),
+            biome(Id::Promenade, 2),
+            biome(Id::Ossuary, 2),
+            biome(Id::Bridge, 1),
+        ];
+        let path: Vec<&Biome> = biomes.iter().collect();
+
+        let collectibles = Collectibles::for_path(&path, 0);
+
+        let conversions: Vec<u32> = collectibles
+            .line_items
+            .iter()
+            .map(|item| item.fragment_conversions)
+            .collect();
+        assert_eq!(conversions, vec![0, 1, 0, 1]);
+        assert_eq!(collectibles.leftover_fragments, 0);
+    }
+
+    #[test]
+    fn should_explain_a_route_biome_by_biome() {
+        let biomes = core::get_biomes().unwrap();
+        let path: Vec<&Biome> = ["prisonquart", "arboretum", "prisondepths", "ancientsewers"]
+            .iter()
+            .map(|id| biomes.iter().find(|biome| biome.id == Id::new(id)).unwrap())
+            .collect();
+
+        let explanation = explain(&path, 3);
+
+        assert_eq!(explanation.len(), path.len());
+        for (i, step) in explanation.iter().enumerate() {
+            assert_eq!(step.line_item.biome, path[i].id);
+            assert_eq!(
+                step.line_item.scroll_fragments,
+                path[i].scroll_fragments.get_fragments(3) as u32
+            );
+            assert_eq!(step.running_totals, RouteTotals::of(&path[..=i], 3));
+            assert_eq!(
+                step.converts_fragments,
+                step.line_item.fragment_conversions > 0
+            );
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::error::Error;
+use crate::json::models::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An exit between two biomes, `{"from": "promenade", "to": "ossuary"}`
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Hash, Ord, PartialOrd)]
+pub struct ExitId {
+    pub from: Id,
+    pub to: Id,
+}
+
+impl fmt::Display for ExitId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.from, self.to)
+    }
+}
+
+/// Where a route starts and ends and what it has to go through or stay away from, on top of
+/// the blacklist
+#[derive(Serialize, Deserialize, Debug, Default, Eq, PartialEq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Constraints {
+    /// the first biome of the dataset if not set
+    pub start: Option<Id>,
+    /// any biome a run can end in if not set, see `Biome::is_final`
+    pub end: Option<Id>,
+    pub required_biomes: Vec<Id>,
+    pub required_exits: Vec<ExitId>,
+    pub forbidden_exits: Vec<ExitId>,
+}
+
+/// A single one of the `Constraints`, so errors can tell which one couldn't be met
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Constraint {
+    RequiredBiome(Id),
+    RequiredExit(ExitId),
+    ForbiddenExit(ExitId),
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::RequiredBiome(id) => write!(f, "through {}", id),
+            Constraint::RequiredExit(exit) => write!(f, "through the {} exit", exit),
+            Constraint::ForbiddenExit(exit) => write!(f, "avoiding the {} exit", exit),
+        }
+    }
+}
+
+impl Constraints {
+    pub fn is_empty(&self) -> bool {
+        self.required_biomes.is_empty()
+            && self.required_exits.is_empty()
+            && self.forbidden_exits.is_empty()
+    }
+
+    pub fn constraints(&self) -> Vec<Constraint> {
+        let required_biomes = self
+            .required_biomes
+            .iter()
+            .cloned()
+            .map(Constraint::RequiredBiome);
+        let required_exits = self
+            .required_exits
+            .iter()
+            .cloned()
+            .map(Constraint::RequiredExit);
+        let forbidden_exits = self
+            .forbidden_exits
+            .iter()
+            .cloned()
+            .map(Constraint::ForbiddenExit);
+        required_biomes
+            .chain(required_exits)
+            .chain(forbidden_exits)
+            .collect()
+    }
+
+    fn with(mut self, constraint: Constraint) -> Self {
+        match constraint {
+            Constraint::RequiredBiome(id) => self.required_biomes.push(id),
+            Constraint::RequiredExit(exit) => self.required_exits.push(exit),
+            Constraint::ForbiddenExit(exit) => self.forbidden_exits.push(exit),
+        }
+        self
+    }
+
+    /// Adds the constraints one by one and returns the first one `is_met` rejects, that is the
+    /// one that can't be met together with the ones before it
+    pub fn first_unmet(&self, mut is_met: impl FnMut(&Constraints) -> bool) -> Option<Constraint> {
+        let mut constraints = Constraints {
+            start: self.start.clone(),
+            end: self.end.clone(),
+            ..Constraints::default()
+        };
+        for constraint in self.constraints() {
+            constraints = constraints.with(constraint.clone());
+            if !is_met(&constraints) {
+                return Some(constraint);
+            }
+        }
+        None
+    }
+
+    /// The biome routes start in
+    pub fn start(&self, biomes: &[Biome]) -> Result<Id, Error> {
+        match &self.start {
+            Some(start) => Ok(start.clone()),
+            None => biomes.first().map(|biome| biome.id.clone()).ok_or_else(|| {
+                Error::InvalidDataset("Can't find routes in an empty dataset".to_string())
+            }),
+        }
+    }
+
+    /// The biomes routes can end in
+    pub fn ends(&self, biomes: &[Biome]) -> Vec<Id> {
+        match &self.end {
+            Some(end) => vec![end.clone()],
+            None => biomes
+                .iter()
+                .filter(|biome| biome.is_final())
+                .map(|biome| biome.id.clone())
+                .collect(),
+        }
+    }
+
+    /// Whether routes start and end where they always have, so the precomputed paths can be used
+    pub fn has_default_ends(&self) -> bool {
+        self.start.is_none() && self.end.is_none()
+    }
+
+    /// Describes a route that can't be found, e.g. "No route from prisonquart to throne or
+    /// observatory through ossuary"
+    pub fn no_route(&self, biomes: &[Biome], unmet: Option<Constraint>) -> Error {
+        let start = self
+            .start(biomes)
+            .map(|start| start.to_string())
+            .unwrap_or_default();
+        let ends: Vec<String> = self
+            .ends(biomes)
+            .iter()
+            .map(|end| end.to_string())
+            .collect();
+        Error::NoRoute(match unmet {
+            Some(constraint) => format!(
+                "No route from {} to {} {}",
+                start,
+                ends.join(" or "),
+                constraint
+            ),
+            None => format!("No route from {} to {}", start, ends.join(" or ")),
+        })
+    }
+
+    /// Fails on biomes and exits that aren't in the dataset
+    pub fn validate(&self, biomes: &[Biome]) -> Result<(), Error> {
+        for id in self.start.iter().chain(&self.end) {
+            if !biomes.iter().any(|biome| &biome.id == id) {
+                return Err(Error::InvalidInput(format!(
+                    "Can't route from or to unknown biome {}",
+                    id
+                )));
+            }
+        }
+        for id in &self.required_biomes {
+            if !biomes.iter().any(|biome| &biome.id == id) {
+                return Err(Error::InvalidInput(format!(
+                    "Can't route through unknown biome {}",
+                    id
+                )));
+            }
+        }
+        for exit in self.required_exits.iter().chain(&self.forbidden_exits) {
+            let exists = biomes.iter().any(|biome| {
+                biome.id == exit.from && biome.exits.iter().any(|e| e.destination == exit.to)
+            });
+            if !exists {
+                return Err(Error::InvalidInput(format!("There is no {} exit", exit)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The required biomes and both ends of the required exits. Searches look them up once and
+    /// pass them to `allows_exit_past` instead of having every exit look them up again.
+    pub(crate) fn required<'b>(&self, biomes: &'b [Biome]) -> Vec<&'b Biome> {
+        let ids = self.required_biomes.iter().chain(
+            self.required_exits
+                .iter()
+                .flat_map(|exit| vec![&exit.from, &exit.to]),
+        );
+        ids.filter_map(|id| biomes.iter().find(|biome| &biome.id == id))
+            .collect()
+    }
+
+    /// Whether a route can start in `start`, nothing can be required before it or next to it
+    pub fn allows_start(&self, biomes: &[Biome], start: &Biome) -> bool {
+        self.required(biomes).iter().all(|required| {
+            required.row > start.row || required.row == start.row && required.id == start.id
+        })
+    }
+
+    /// Whether a route can take the exit from `from` to `to`. Rows only ever go up so a route can
+    /// only go through a required biome by landing in its row, so exits that skip over that row
+    /// or land next to the required biome are ruled out, as are the other exits of a biome with
+    /// a required exit.
+    pub fn allows_exit(&self, biomes: &[Biome], from: &Biome, to: &Biome) -> bool {
+        self.allows_exit_past(&self.required(biomes), from, to)
+    }
+
+    /// `allows_exit` with the biomes `required` returned
+    pub(crate) fn allows_exit_past(&self, required: &[&Biome], from: &Biome, to: &Biome) -> bool {
+        let is = |exit: &ExitId| exit.from == from.id && exit.to == to.id;
+        if self.forbidden_exits.iter().any(is) {
+            return false;
+        }
+        if self
+            .required_exits
+            .iter()
+            .any(|exit| exit.from == from.id && exit.to != to.id)
+        {
+            return false;
+        }
+        required.iter().all(|required| {
+            let skipped = from.row < required.row && required.row < to.row;
+            let beside = to.row == required.row && to.id != required.id;
+            !skipped && !beside
+        })
+    }
+
+    /// Whether a route can end in `end`, nothing can be required after it or next to it
+    pub fn allows_end(&self, biomes: &[Biome], end: &Biome) -> bool {
+        self.required(biomes).iter().all(|required| {
+            required.row < end.row || required.row == end.row && required.id == end.id
+        })
+    }
+
+    pub fn allows_path(&self, biomes: &[Biome], path: &[&Biome]) -> bool {
+        match (path.first(), path.last()) {
+            (Some(start), Some(end)) => {
+                let required = self.required(biomes);
+                self.allows_start(biomes, start)
+                    && self.allows_end(biomes, end)
+                    && path
+                        .windows(2)
+                        .all(|pair| self.allows_exit_past(&required, pair[0], pair[1]))
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+    use crate::path;
+
+    fn exit(from: Id, to: Id) -> ExitId {
+        ExitId { from, to }
+    }
+
+    #[test]
+    fn should_match_constraints_on_every_path() {
+        let biomes = core::get_biomes().unwrap();
+        let paths = path::find_paths_between(&biomes, &Id::Prisonquart, &[Id::Throne]).unwrap();
+        let constraints = Constraints {
+            required_biomes: vec![Id::Ossuary, Id::Cavern],
+            required_exits: vec![exit(Id::Bridge, Id::Slumbering)],
+            forbidden_exits: vec![exit(Id::Haven, Id::Throne)],
+            ..Constraints::default()
+        };
+
+        let mut allowed = 0;
+        for path in &paths {
+            let ids: Vec<&Id> = path.path.iter().map(|biome| &biome.id).collect();
+            let takes = |exit: &ExitId| {
+                ids.windows(2)
+                    .any(|pair| pair[0] == &exit.from && pair[1] == &exit.to)
+            };
+            let expected = constraints
+                .required_biomes
+                .iter()
+                .all(|id| ids.contains(&id))
+                && constraints.required_exits.iter().all(takes)
+                && !constraints.forbidden_exits.iter().any(takes);
+
+            assert_eq!(
+                constraints.allows_path(&biomes, &path.path),
+                expected,
+                "{:?}",
+                ids
+            );
+            if expected {
+                allowed += 1;
+            }
+        }
+        assert!(allowed > 0);
+    }
+
+    #[test]
+    fn should_name_the_first_unmet_constraint() {
+        let constraints = Constraints {
+            required_biomes: vec![Id::Ossuary, Id::Crypt, Id::Graveyard],
+            ..Constraints::default()
+        };
+
+        let unmet = constraints.first_unmet(|constraints| constraints.required_biomes.len() < 3);
+
+        assert_eq!(unmet, Some(Constraint::RequiredBiome(Id::Graveyard)));
+        assert_eq!(unmet.unwrap().to_string(), "through graveyard");
+    }
+}
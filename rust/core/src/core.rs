@@ -0,0 +1,1608 @@
+use serde::Serialize;
+
+use crate::blacklist::Blacklist;
+use crate::chests::CursedChests;
+use crate::collectibles;
+use crate::collectibles::{BiomeExplanation, Collectibles};
+use crate::constraints::Constraints;
+use crate::diff;
+use crate::diff::RouteDiff;
+use crate::error::Error;
+use crate::graph;
+use crate::graph::Graph;
+use crate::json;
+use crate::json::models::*;
+use crate::json::validation;
+use crate::json::validation::ValidationError;
+use crate::lazies;
+use crate::pareto;
+use crate::pareto::{Objective, ParetoRoute};
+use crate::path;
+use crate::path::{DisabledBiomes, RenderablePath};
+use crate::reach;
+use crate::reach::MinimumBossCells;
+use crate::scoring::{compare_scores, RouteScorer, RouteTotals, ScrollScorer};
+use crate::simulation;
+use crate::simulation::Simulation;
+use std::fmt::Debug;
+
+pub fn get_biomes() -> Result<Vec<Biome>, Error> {
+    get_biomes_from_str(json::get_json())
+}
+
+/// Runs the planner over `biomes`, or over the compiled-in biomes.json if no dataset is given
+pub fn get_biomes_and_paths(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    biomes: Option<Vec<Biome>>,
+) -> Result<(Vec<Vec<Biome>>, Vec<RenderablePath>), Error> {
+    validate_dataset(biomes.as_ref())?;
+    biomes_and_paths(blacklist, progress, constraints, biomes)
+}
+
+/// `get_biomes_and_paths` for biomes that were validated already
+fn biomes_and_paths(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    biomes: Option<Vec<Biome>>,
+) -> Result<(Vec<Vec<Biome>>, Vec<RenderablePath>), Error> {
+    let (paths, disabled_biomes) =
+        path::get_paths(biomes.as_ref(), &blacklist, progress, constraints)?;
+
+    let biomes: Vec<Biome> = biomes.unwrap_or_else(|| {
+        let b: &Vec<Biome> = &lazies::BIOMES;
+        b.clone()
+    });
+
+    let biomes = disable_biomes(biomes, disabled_biomes);
+    let biomes = order_biomes_by_tier(biomes)?;
+
+    Ok((biomes, paths))
+}
+
+/// Everything the map needs, this is what `getBiomes` hands to JS
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct BiomeMap {
+    /// the biomes row by row, rows without biomes are left out
+    pub tiers: Vec<Vec<Biome>>,
+    pub paths: Vec<RenderablePath>,
+    /// the enabled biomes, in tier order
+    pub reachable: Vec<Id>,
+    /// only there if a scorer was passed and some route is enabled
+    pub best_route: Option<RankedRoute>,
+}
+
+/// `get_biomes_and_paths` with the reachable biomes picked out, and the best route if there's a
+/// `scorer`. No route at all isn't an error here, the map just has no best route.
+pub fn get_biome_map(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    scorer: Option<&dyn RouteScorer>,
+    biomes: Option<Vec<Biome>>,
+) -> Result<BiomeMap, Error> {
+    validate_dataset(biomes.as_ref())?;
+    let best_route = match scorer {
+        Some(scorer) => with_valid_graph(biomes.as_ref(), constraints, |graph, start, ends| {
+            let route = best_route_in(
+                graph,
+                start,
+                ends,
+                &blacklist,
+                progress,
+                constraints,
+                scorer,
+            );
+            match route {
+                Ok(route) => Ok(Some(route)),
+                Err(Error::NoRoute(_)) => Ok(None),
+                Err(error) => Err(error),
+            }
+        })?,
+        None => None,
+    };
+    let (tiers, paths) = biomes_and_paths(blacklist, progress, constraints, biomes)?;
+
+    Ok(BiomeMap::new(tiers, paths, best_route))
+}
+
+impl BiomeMap {
+    pub(crate) fn new(
+        tiers: Vec<Vec<Biome>>,
+        paths: Vec<RenderablePath>,
+        best_route: Option<RankedRoute>,
+    ) -> Self {
+        let reachable = tiers
+            .iter()
+            .flatten()
+            .filter(|biome| biome.enabled)
+            .map(|biome| biome.id.clone())
+            .collect();
+        BiomeMap {
+            tiers,
+            paths,
+            reachable,
+            best_route,
+        }
+    }
+}
+
+pub fn get_biomes_and_paths_from_str(
+    json: &str,
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<(Vec<Vec<Biome>>, Vec<RenderablePath>), Error> {
+    let biomes = get_biomes_from_str(json)?;
+    get_biomes_and_paths(blacklist, progress, constraints, Some(biomes))
+}
+
+pub fn get_biomes_from_str(json: &str) -> Result<Vec<Biome>, Error> {
+    serde_json::from_str(json)
+        .map_err(|err| Error::InvalidDataset(format!("Failed to parse json: {}", err)))
+}
+
+/// Fails with every structural problem in the dataset, see `validation::validate`
+pub fn validate_biomes(biomes: &[Biome]) -> Result<(), Error> {
+    let errors: Vec<ValidationError> = validation::validate(biomes);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+    Err(Error::InvalidDataset(format!(
+        "Invalid dataset: {}",
+        errors.join(", ")
+    )))
+}
+
+/// Validates the dataset if one was passed, the compiled-in biomes.json doesn't need it
+fn validate_dataset(biomes: Option<&Vec<Biome>>) -> Result<(), Error> {
+    match biomes {
+        Some(biomes) => validate_biomes(biomes),
+        None => Ok(()),
+    }
+}
+
+/// The validated dataset, or the compiled-in biomes if none was passed
+fn dataset(biomes: Option<&Vec<Biome>>) -> Result<&[Biome], Error> {
+    validate_dataset(biomes)?;
+    Ok(biomes.unwrap_or(&*lazies::BIOMES))
+}
+
+// todo this shouldn't return result, it can only error due to code error
+pub(crate) fn order_biomes_by_tier(biomes: Vec<Biome>) -> Result<Vec<Vec<Biome>>, Error> {
+    let init: Vec<Vec<Biome>> = (0..validation::MAX_ROWS).map(|_| vec![]).collect();
+
+    let biomes: Vec<Vec<Biome>> = biomes
+        .into_iter()
+        .map(|biome| (biome.row, biome))
+        .try_fold(init, |mut acc, (tier, biome)| {
+            let biomes = acc.get_mut(tier - 1);
+            match biomes {
+                Some(b) => {
+                    b.push(biome);
+                    Ok(acc)
+                }
+                None => Err(Error::InvalidDataset(format!("no row at {}", tier))),
+            }
+        })?;
+
+    let biomes: Vec<Vec<Biome>> = biomes.into_iter().filter(|tier| !tier.is_empty()).collect();
+
+    Ok(biomes)
+}
+
+/// The expected scrolls of the best of the `paths` and the path itself, none if there are no paths
+pub fn get_path_with_most_scrolls<'b>(
+    paths: &'b [Vec<&'b Biome>],
+    boss_cells: u8,
+    include_dual_scrolls: bool,
+) -> Option<(u8, &'b Vec<&'b Biome>)> {
+    let scorer = ScrollScorer {
+        include_dual_scrolls,
+    };
+    let (scrolls, path) = rank_paths(paths.iter(), boss_cells, &scorer)
+        .into_iter()
+        .next()?;
+
+    Some((scrolls.round() as u8, path))
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct RankedRoute {
+    pub route: Vec<Id>,
+    /// what the scorer made of the route, its expected scrolls with the default scorer
+    pub score: f64,
+    /// how much lower the score is than the best route's
+    pub behind_best: f64,
+    pub collectibles: Collectibles,
+    pub cursed_chests: CursedChests,
+}
+
+/// The `k` enabled routes `scorer` likes best, best first
+pub fn get_top_routes(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    scorer: &dyn RouteScorer,
+    k: usize,
+    biomes: Option<Vec<Biome>>,
+) -> Result<Vec<RankedRoute>, Error> {
+    validate_dataset(biomes.as_ref())?;
+
+    let paths = path::get_toggleable_paths(biomes.as_ref(), &blacklist, progress, constraints)?;
+    let paths = paths
+        .iter()
+        .filter(|path| path.enabled)
+        .map(|path| &path.path);
+
+    Ok(top_routes(paths, progress.boss_cells, scorer, k))
+}
+
+pub(crate) fn top_routes<'p, 'b: 'p>(
+    paths: impl Iterator<Item = &'p Vec<&'b Biome>>,
+    boss_cells: u8,
+    scorer: &dyn RouteScorer,
+    k: usize,
+) -> Vec<RankedRoute> {
+    let ranked = rank_paths(paths, boss_cells, scorer);
+    let best = ranked.first().map(|(score, _)| *score).unwrap_or(0.);
+
+    ranked
+        .into_iter()
+        .take(k)
+        .map(|(score, path)| RankedRoute {
+            route: path.iter().map(|biome| biome.id.clone()).collect(),
+            score,
+            behind_best: best - score,
+            collectibles: Collectibles::for_path(path, boss_cells),
+            cursed_chests: CursedChests::for_path(path),
+        })
+        .collect()
+}
+
+/// The route `scorer` likes best, found without enumerating every path
+pub fn get_best_route(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    scorer: &dyn RouteScorer,
+    biomes: Option<Vec<Biome>>,
+) -> Result<RankedRoute, Error> {
+    with_graph(biomes.as_ref(), constraints, |graph, start, ends| {
+        best_route_in(
+            graph,
+            start,
+            ends,
+            &blacklist,
+            progress,
+            constraints,
+            scorer,
+        )
+    })
+}
+
+/// `get_best_route` on a graph that's already built, fails with the constraint that can't be met
+pub(crate) fn best_route_in(
+    graph: &Graph,
+    start: &Id,
+    ends: &[Id],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    scorer: &dyn RouteScorer,
+) -> Result<RankedRoute, Error> {
+    let best_route = |constraints: &Constraints| {
+        graph::best_route(graph, start, ends, blacklist, progress, constraints, scorer)
+    };
+    let (score, path) = best_route(constraints).ok_or_else(|| {
+        let unmet = constraints.first_unmet(|constraints| best_route(constraints).is_some());
+        constraints.no_route(graph.biomes, unmet)
+    })?;
+
+    Ok(RankedRoute {
+        route: path.iter().map(|biome| biome.id.clone()).collect(),
+        score,
+        behind_best: 0.,
+        collectibles: Collectibles::for_path(&path, progress.boss_cells),
+        cursed_chests: CursedChests::for_path(&path),
+    })
+}
+
+/// Every route that no other route beats on all of the `objectives`
+pub fn get_pareto_routes(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    objectives: &[Objective],
+    biomes: Option<Vec<Biome>>,
+) -> Result<Vec<ParetoRoute>, Error> {
+    with_graph(biomes.as_ref(), constraints, |graph, start, ends| {
+        Ok(pareto::pareto_routes(
+            graph,
+            start,
+            ends,
+            &blacklist,
+            progress,
+            constraints,
+            objectives,
+        ))
+    })
+}
+
+/// Plays `route` `runs` times, see `simulation::simulate`
+pub fn simulate_route(
+    route: &[Id],
+    boss_cells: u8,
+    include_dual_scrolls: bool,
+    runs: u32,
+    seed: u64,
+    biomes: Option<Vec<Biome>>,
+) -> Result<Simulation, Error> {
+    let biomes = dataset(biomes.as_ref())?;
+    let path = resolve_route(biomes, route)?;
+
+    simulation::simulate(&path, boss_cells, include_dual_scrolls, runs, seed)
+}
+
+/// Goes through the route, a list of biome ids, biome by biome to show what each of them adds
+pub fn explain_route(
+    route: &[Id],
+    boss_cells: u8,
+    biomes: Option<Vec<Biome>>,
+) -> Result<Vec<BiomeExplanation>, Error> {
+    let biomes = dataset(biomes.as_ref())?;
+    let path = resolve_route(biomes, route)?;
+
+    Ok(collectibles::explain(&path, boss_cells))
+}
+
+/// The fewest boss cells needed to get to every biome and through every exit from the start,
+/// the end and the rest of the constraints don't matter
+pub fn get_minimum_boss_cells(
+    blacklist: Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    biomes: Option<Vec<Biome>>,
+) -> Result<MinimumBossCells, Error> {
+    with_graph(biomes.as_ref(), constraints, |graph, start, _| {
+        Ok(reach::minimum_boss_cells(
+            graph, start, &blacklist, progress,
+        ))
+    })
+}
+
+/// Where two routes, lists of biome ids, part ways and what the second one picks up that the
+/// first one doesn't
+pub fn diff_routes(
+    first: &[Id],
+    second: &[Id],
+    boss_cells: u8,
+    biomes: Option<Vec<Biome>>,
+) -> Result<RouteDiff, Error> {
+    let biomes = dataset(biomes.as_ref())?;
+    let first = resolve_route(biomes, first)?;
+    let second = resolve_route(biomes, second)?;
+
+    Ok(diff::diff(&first, &second, boss_cells))
+}
+
+/// Looks up the biomes of a route given by ids, failing if there's no exit between two of them
+fn resolve_route<'b>(biomes: &'b [Biome], route: &[Id]) -> Result<Vec<&'b Biome>, Error> {
+    if route.is_empty() {
+        return Err(Error::InvalidInput("The route is empty".to_string()));
+    }
+    let path = route
+        .iter()
+        .map(|id| {
+            biomes
+                .iter()
+                .find(|biome| &biome.id == id)
+                .ok_or_else(|| Error::InvalidInput(format!("No biome with id {}", id)))
+        })
+        .collect::<Result<Vec<&Biome>, Error>>()?;
+    for pair in path.windows(2) {
+        if !pair[0]
+            .exits
+            .iter()
+            .any(|exit| exit.destination == pair[1].id)
+        {
+            return Err(Error::InvalidInput(format!(
+                "No exit from {} to {}",
+                pair[0].id, pair[1].id
+            )));
+        }
+    }
+    Ok(path)
+}
+
+/// Runs `f` on the graph of `biomes`, or on the compiled-in graph, and the start and ends of
+/// the routes
+fn with_graph<T>(
+    biomes: Option<&Vec<Biome>>,
+    constraints: &Constraints,
+    f: impl FnOnce(&Graph, &Id, &[Id]) -> Result<T, Error>,
+) -> Result<T, Error> {
+    validate_dataset(biomes)?;
+    with_valid_graph(biomes, constraints, f)
+}
+
+/// `with_graph` for biomes that were validated already
+fn with_valid_graph<T>(
+    biomes: Option<&Vec<Biome>>,
+    constraints: &Constraints,
+    f: impl FnOnce(&Graph, &Id, &[Id]) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let graph;
+    let graph: &Graph = match biomes {
+        Some(biomes) => {
+            graph = Graph::new(biomes)?;
+            &graph
+        }
+        None => &lazies::GRAPH,
+    };
+    constraints.validate(graph.biomes)?;
+    let start = constraints.start(graph.biomes)?;
+    let ends = constraints.ends(graph.biomes);
+
+    f(graph, &start, &ends)
+}
+
+/// Sorts paths by score, paths with the same score are sorted by their biome ids so ties are
+/// always broken the same way
+fn rank_paths<'p, 'b: 'p>(
+    paths: impl Iterator<Item = &'p Vec<&'b Biome>>,
+    boss_cells: u8,
+    scorer: &dyn RouteScorer,
+) -> Vec<(f64, &'p Vec<&'b Biome>)> {
+    let mut paths_with_scores: Vec<(f64, &Vec<&Biome>)> = paths
+        .map(|path| (scorer.score(&RouteTotals::of(path, boss_cells)), path))
+        .collect();
+
+    paths_with_scores.sort_by(|(left_score, left_path), (right_score, right_path)| {
+        compare_scores(*right_score, *left_score).then_with(|| {
+            let left_ids = left_path.iter().map(|biome| &biome.id);
+            let right_ids = right_path.iter().map(|biome| &biome.id);
+            left_ids.cmp(right_ids)
+        })
+    });
+
+    paths_with_scores
+}
+
+pub(crate) fn disable_biomes(biomes: Vec<Biome>, mut disabled: DisabledBiomes) -> Vec<Biome> {
+    biomes
+        .into_iter()
+        .map(|mut biome| {
+            let reasons = disabled.remove(&biome.id);
+            biome.enabled = reasons.is_none();
+            biome.disabled_reasons = reasons.unwrap_or_default();
+            biome
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::ExitId;
+    use crate::scoring::WeightedScorer;
+
+    #[test]
+    fn should_gracefully_fail() {
+        let fail = get_biomes_from_str("qweqeqqwe{{{{");
+        assert!(fail.is_err());
+        if let Err(error) = fail {
+            assert_eq!(
+                error,
+                Error::InvalidDataset(
+                    "Failed to parse json: expected value at line 1 column 1".to_string()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_id_serde() {
+        let json = serde_json::to_string(&Id::Castle).unwrap();
+        assert_eq!(json, String::from("\"castle\""));
+
+        let id: Id = serde_json::from_str("\"castle\"").unwrap();
+        assert_eq!(id, Id::Castle);
+    }
+
+    #[test]
+    fn should_load_biomes_unknown_to_the_code() {
+        let json = r#"[
+            {"id": "prisonquart", "name": "Prisoners' Quarters", "row": 1, "column": 1,
+             "power_scrolls": 2, "dual_power_scrolls": 0, "cursed_chest_chance": 1,
+             "scroll_fragments": {}, "gear_level": 1,
+             "exits": [{"destination": "distillery"}]},
+            {"id": "distillery", "name": "Distillery", "row": 2, "column": 1,
+             "power_scrolls": 1, "dual_power_scrolls": 2, "cursed_chest_chance": 10,
+             "scroll_fragments": {}, "gear_level": 2,
+             "exits": [{"destination": "throne"}]},
+            {"id": "throne", "name": "Throne Room", "row": 3, "column": 1,
+             "power_scrolls": 0, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+             "scroll_fragments": {}, "gear_level": 0, "exits": []}
+        ]"#;
+
+        let biomes = get_biomes_from_str(json).unwrap();
+        assert_eq!(biomes[1].id, Id::new("distillery"));
+        assert_eq!(biomes[0].exits[0].destination.as_str(), "distillery");
+
+        let (_, paths) = get_biomes_and_paths(
+            vec![].into(),
+            &0.into(),
+            &Constraints::default(),
+            Some(biomes),
+        )
+        .unwrap();
+        let paths: Vec<&str> = paths.iter().map(|path| path.id.as_str()).collect();
+        assert_eq!(paths, vec!["prisonquart-distillery", "distillery-throne"]);
+    }
+
+    #[test]
+    fn ids_should_compare_by_their_str() {
+        let left = Id::new("distillery");
+        let right: Id = serde_json::from_str("\"distillery\"").unwrap();
+
+        assert_eq!(left, right);
+        assert!(std::ptr::eq(left.as_str(), left.clone().as_str()));
+        assert_eq!(Id::new("castle"), Id::Castle);
+        assert_ne!(Id::new("castle"), Id::Crypt);
+    }
+
+    #[test]
+    fn parse_json() {
+        let biomes = get_biomes().unwrap();
+        assert_eq!(biomes.len(), 25);
+    }
+
+    #[test]
+    fn should_plan_over_runtime_dataset() {
+        let json = r#"[
+            {"id": "prisonquart", "name": "Prisoners' Quarters", "row": 1, "column": 1,
+             "power_scrolls": 2, "dual_power_scrolls": 0, "cursed_chest_chance": 1,
+             "scroll_fragments": {}, "gear_level": 1,
+             "exits": [{"destination": "promenade"}, {"destination": "toxicsewers"}]},
+            {"id": "promenade", "name": "Promenade of the Condemned", "row": 2, "column": 1,
+             "power_scrolls": 1, "dual_power_scrolls": 2, "cursed_chest_chance": 10,
+             "scroll_fragments": {}, "gear_level": 2,
+             "exits": [{"destination": "throne"}]},
+            {"id": "toxicsewers", "name": "Toxic Sewers", "row": 2, "column": 2,
+             "power_scrolls": 1, "dual_power_scrolls": 2, "cursed_chest_chance": 10,
+             "scroll_fragments": {}, "gear_level": 2,
+             "exits": [{"destination": "throne", "boss_cell_requirement": 1}]},
+            {"id": "throne", "name": "Throne Room", "row": 3, "column": 1,
+             "power_scrolls": 0, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+             "scroll_fragments": {}, "gear_level": 0, "exits": []}
+        ]"#;
+
+        let (biomes, paths) =
+            get_biomes_and_paths_from_str(json, vec![].into(), &0.into(), &Constraints::default())
+                .unwrap();
+
+        let biomes: Vec<(&Id, bool)> = biomes
+            .iter()
+            .flatten()
+            .map(|biome| (&biome.id, biome.enabled))
+            .collect();
+        assert_eq!(
+            biomes,
+            vec![
+                (&Id::Prisonquart, true),
+                (&Id::Promenade, true),
+                (&Id::Toxicsewers, false),
+                (&Id::Throne, true),
+            ]
+        );
+
+        let paths: Vec<(&str, bool)> = paths
+            .iter()
+            .map(|path| (path.id.as_str(), path.enabled))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("prisonquart-promenade", true),
+                ("promenade-throne", true),
+                ("prisonquart-toxicsewers", false),
+                ("toxicsewers-throne", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn runtime_dataset_should_match_compiled_in_dataset() {
+        let compiled_in = get_biomes_and_paths(
+            vec![Id::Ossuary].into(),
+            &3.into(),
+            &Constraints::default(),
+            None,
+        )
+        .unwrap();
+        let runtime = get_biomes_and_paths_from_str(
+            json::get_json(),
+            vec![Id::Ossuary].into(),
+            &3.into(),
+            &Constraints::default(),
+        )
+        .unwrap();
+
+        assert_eq!(compiled_in, runtime);
+    }
+
+    #[test]
+    fn should_not_plan_over_invalid_dataset() {
+        let result = get_biomes_and_paths(
+            vec![].into(),
+            &5.into(),
+            &Constraints::default(),
+            Some(vec![]),
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidDataset(
+                "Invalid dataset: throne.id: the dataset has no throne room".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn biome_from_tuple() {
+        let biome: Biome = (Id::Prisonquart, vec![Id::Castle, Id::Crypt]).into();
+
+        let expected = Biome {
+            id: Id::Prisonquart,
+            name: Id::Prisonquart.to_string(),
+            row: 0,
+            column: 0,
+            power_scrolls: 0,
+            dual_power_scrolls: 0,
+            cursed_chest_chance: 0,
+            scroll_fragments: Default::default(),
+            gear_level: 0,
+            exits: vec![
+                Exit {
+                    destination: Id::Castle,
+                    boss_cell_requirement: None,
+                    power_scrolls: None,
+                    requirements: vec![],
+                },
+                Exit {
+                    destination: Id::Crypt,
+                    boss_cell_requirement: None,
+                    power_scrolls: None,
+                    requirements: vec![],
+                },
+            ],
+            enabled: true,
+            disabled_reasons: vec![],
+        };
+
+        assert_eq!(biome, expected);
+    }
+
+    #[test]
+    fn find_path_between_nodes() {
+        let input: Vec<Biome> = vec![
+            (Id::Prisonquart, vec![Id::Corruptedprison, Id::Bridge]).into(),
+            (Id::Corruptedprison, vec![]).into(),
+            (Id::Bridge, vec![Id::Throne, Id::Slumbering]).into(),
+            (Id::Slumbering, vec![Id::Throne]).into(),
+            (Id::Throne, vec![]).into(),
+        ];
+
+        let paths = path::find_paths_between(&input, &Id::Prisonquart, &[Id::Throne]);
+        assert!(paths.is_ok());
+        let paths = paths.unwrap();
+        let paths: Vec<Vec<&Biome>> = paths
+            .into_iter()
+            .map(|p| p.path.into_iter().collect())
+            .collect();
+
+        let paths_string: Vec<Vec<&Id>> =
+            paths.into_iter().map(|path| path_to_ids(&path)).collect();
+
+        // todo maybe clone to remove &
+        assert_eq!(
+            paths_string,
+            vec![
+                vec![&Id::Prisonquart, &Id::Bridge, &Id::Slumbering, &Id::Throne],
+                vec![&Id::Prisonquart, &Id::Bridge, &Id::Throne]
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_paths_for_actual_data() {
+        let biomes = get_biomes().unwrap();
+        let paths = path::find_paths_between(&biomes, &Id::Prisonquart, &[Id::Observatory]);
+        assert!(paths.is_ok());
+        let paths = paths.unwrap();
+        let paths: Vec<Vec<&Biome>> = paths
+            .into_iter()
+            .map(|p| p.path.into_iter().collect())
+            .collect();
+
+        let mut result: Vec<(Collectibles, Vec<&String>)> = paths
+            .into_iter()
+            .map(|path| (Collectibles::for_path(&path, 5), path_to_names(&path)))
+            .collect();
+
+        result.sort_by_key(|(collectibles, _)| collectibles.totals.power_scrolls);
+        result.reverse();
+    }
+
+    #[test]
+    fn should_count_the_scrolls_of_the_exit_into_the_throne_room() {
+        let json = |exit_scrolls: u8| {
+            format!(
+                r#"[
+                {{"id": "prisonquart", "name": "Prisoners' Quarters", "row": 1, "column": 1,
+                 "power_scrolls": 0, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+                 "scroll_fragments": {{}}, "gear_level": 1,
+                 "exits": [{{"destination": "haven"}}, {{"destination": "castle"}}]}},
+                {{"id": "haven", "name": "Guardian's Haven", "row": 2, "column": 1,
+                 "power_scrolls": 0, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+                 "scroll_fragments": {{}}, "gear_level": 2,
+                 "exits": [{{"destination": "throne", "power_scrolls": {}}}]}},
+                {{"id": "castle", "name": "High Peak Castle", "row": 2, "column": 2,
+                 "power_scrolls": 1, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+                 "scroll_fragments": {{}}, "gear_level": 2,
+                 "exits": [{{"destination": "throne"}}]}},
+                {{"id": "throne", "name": "Throne Room", "row": 3, "column": 1,
+                 "power_scrolls": 0, "dual_power_scrolls": 0, "cursed_chest_chance": 0,
+                 "scroll_fragments": {{}}, "gear_level": 3, "exits": []}}
+            ]"#,
+                exit_scrolls
+            )
+        };
+        let best_route = |exit_scrolls: u8| {
+            get_best_route(
+                Blacklist::default(),
+                &5.into(),
+                &Constraints::default(),
+                &ScrollScorer::default(),
+                Some(get_biomes_from_str(&json(exit_scrolls)).unwrap()),
+            )
+            .unwrap()
+        };
+
+        let route = best_route(2);
+        assert_eq!(route.route, vec![Id::Prisonquart, Id::Haven, Id::Throne]);
+        assert_eq!(route.score, 2.);
+        assert_eq!(route.collectibles.totals.transition_scrolls, 2);
+
+        let route = best_route(0);
+        assert_eq!(route.route, vec![Id::Prisonquart, Id::Castle, Id::Throne]);
+        assert_eq!(route.score, 1.);
+    }
+
+    #[test]
+    fn routes_should_tie_however_their_score_adds_up() {
+        let biome = |id: Id, row: usize, exits: Vec<Id>| Biome {
+            row,
+            column: 1,
+            ..Biome::from((id, exits))
+        };
+        let mut prisonquart = biome(Id::Prisonquart, 1, vec![Id::Promenade, Id::Toxicsewers]);
+        prisonquart.cursed_chest_chance = 12;
+        let mut promenade = biome(Id::Promenade, 2, vec![Id::Throne]);
+        promenade.power_scrolls = 9;
+        let mut toxicsewers = biome(Id::Toxicsewers, 2, vec![Id::Throne]);
+        toxicsewers.column = 2;
+        toxicsewers.power_scrolls = 8;
+        toxicsewers.cursed_chest_chance = 100;
+        let biomes = vec![
+            prisonquart,
+            promenade,
+            toxicsewers,
+            biome(Id::Throne, 3, vec![]),
+        ];
+        let scorer = ScrollScorer::default();
+
+        // 9 + 0.12 and 8 + 1.12 aren't the same f64, the lower ids should still win
+        let best = get_best_route(
+            Blacklist::default(),
+            &0.into(),
+            &Constraints::default(),
+            &scorer,
+            Some(biomes.clone()),
+        )
+        .unwrap();
+        let top = get_top_routes(
+            Blacklist::default(),
+            &0.into(),
+            &Constraints::default(),
+            &scorer,
+            2,
+            Some(biomes),
+        )
+        .unwrap();
+
+        let promenade_route = vec![Id::Prisonquart, Id::Promenade, Id::Throne];
+        assert_eq!(best.route, promenade_route);
+        assert_eq!(top[0].route, promenade_route);
+        assert_eq!(
+            top[1].route,
+            vec![Id::Prisonquart, Id::Toxicsewers, Id::Throne]
+        );
+    }
+
+    #[test]
+    fn should_find_path_with_most_scrolls() {
+        let biomes = get_biomes().unwrap();
+        let paths = path::find_paths_between(&biomes, &Id::Prisonquart, &[Id::Observatory]);
+        assert!(paths.is_ok());
+        let paths = paths.unwrap();
+        let paths: Vec<Vec<&Biome>> = paths.into_iter().map(|p| p.path).collect();
+
+        let (scrolls, _path) = get_path_with_most_scrolls(&paths, 5, false).unwrap();
+
+        assert_eq!(scrolls, 22, "Wrong amount of scrolls in best route");
+        assert_eq!(get_path_with_most_scrolls(&[], 5, false), None);
+    }
+
+    #[test]
+    fn should_rank_top_routes() {
+        let scorer = ScrollScorer::default();
+        let routes = get_top_routes(
+            vec![].into(),
+            &5.into(),
+            &Constraints::default(),
+            &scorer,
+            10,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(routes.len(), 10);
+        assert_eq!(routes[0].score, 22.46);
+        assert_eq!(routes[0].behind_best, 0.);
+        for (better, worse) in routes.iter().zip(routes.iter().skip(1)) {
+            assert!(better.score >= worse.score);
+            assert_eq!(worse.behind_best, routes[0].score - worse.score);
+            if better.score == worse.score {
+                assert!(
+                    better.route < worse.route,
+                    "ties should be sorted by biome ids"
+                );
+            }
+        }
+        assert_eq!(
+            routes,
+            get_top_routes(
+                vec![].into(),
+                &5.into(),
+                &Constraints::default(),
+                &scorer,
+                10,
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn top_routes_should_respect_blacklist_and_boss_cells() {
+        let routes = get_top_routes(
+            vec![Id::Cavern].into(),
+            &0.into(),
+            &Constraints::default(),
+            &ScrollScorer::default(),
+            1000,
+            None,
+        )
+        .unwrap();
+
+        assert!(!routes.is_empty());
+        assert!(routes.len() < 1000);
+        for route in routes {
+            assert!(!route.route.contains(&Id::Cavern));
+            // reaching the sepulcher-haven exit requires 2 boss cells
+            assert!(!route
+                .route
+                .windows(2)
+                .any(|pair| pair == [Id::Sepulcher, Id::Haven]));
+        }
+    }
+
+    #[test]
+    fn best_route_should_match_brute_force() {
+        let biomes = get_biomes().unwrap();
+        let blacklists: Vec<Vec<Id>> = vec![vec![]]
+            .into_iter()
+            .chain(biomes.iter().skip(1).map(|biome| vec![biome.id.clone()]))
+            .chain(vec![
+                vec![Id::Cavern, Id::Castle],
+                vec![Id::Ossuary, Id::Crypt],
+            ])
+            .collect();
+
+        let scorers: Vec<Box<dyn RouteScorer>> = vec![
+            Box::new(ScrollScorer {
+                include_dual_scrolls: false,
+            }),
+            Box::new(ScrollScorer {
+                include_dual_scrolls: true,
+            }),
+            Box::new(WeightedScorer {
+                dual_power_scrolls: 0.5,
+                cursed_chest_chance: -0.05,
+                gear_level: 0.25,
+                ..WeightedScorer::default()
+            }),
+        ];
+
+        for boss_cells in 0..=5 {
+            for (scorer_index, scorer) in scorers.iter().enumerate() {
+                for blacklist in &blacklists {
+                    let progress = boss_cells.into();
+                    let brute_force = get_top_routes(
+                        blacklist.clone().into(),
+                        &progress,
+                        &Constraints::default(),
+                        scorer.as_ref(),
+                        1,
+                        None,
+                    )
+                    .unwrap();
+                    let best = get_best_route(
+                        blacklist.clone().into(),
+                        &progress,
+                        &Constraints::default(),
+                        scorer.as_ref(),
+                        None,
+                    );
+
+                    match brute_force.first() {
+                        Some(expected) => assert_eq!(
+                            best.as_ref(),
+                            Ok(expected),
+                            "boss cells: {}, scorer: {}, blacklist: {:?}",
+                            boss_cells,
+                            scorer_index,
+                            blacklist
+                        ),
+                        None => assert!(best.is_err()),
+                    }
+                }
+            }
+        }
+    }
+
+    /// A made up dataset with few distinct values so plenty of routes tie, some of them only up to
+    /// float noise. Every row but the first and last has `width` biomes.
+    fn generated_biomes(seed: u64, rows: usize, width: usize) -> Vec<Biome> {
+        let mut state = seed;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        let id = |row: usize, column: usize| match row {
+            1 => Id::Prisonquart,
+            _ if row == rows => Id::Throne,
+            _ => Id::new(&format!("r{}c{}", row, column)),
+        };
+        let columns = |row: usize| if row == 1 || row == rows { 1 } else { width };
+
+        let mut biomes = vec![];
+        for row in 1..=rows {
+            for column in 1..=columns(row) {
+                let mut exits: Vec<Exit> = vec![];
+                if row < rows {
+                    for destination in 1..=columns(row + 1) {
+                        if exits.is_empty() || next(2) == 0 {
+                            let mut exit = Exit::from(id(row + 1, destination));
+                            exit.power_scrolls = Some(next(2) as u8).filter(|&n| n > 0);
+                            exits.push(exit);
+                        }
+                    }
+                }
+                let mut biome = Biome::from((id(row, column), row, column));
+                biome.power_scrolls = next(3) as u8;
+                biome.dual_power_scrolls = next(2) as u8;
+                biome.cursed_chest_chance = [0, 12, 100, 112][next(4) as usize];
+                biome.scroll_fragments.zero = Some(next(4) as u8);
+                biome.gear_level = next(3) as u8;
+                biome.exits = exits;
+                biomes.push(biome);
+            }
+        }
+        biomes
+    }
+
+    #[test]
+    fn best_route_should_match_brute_force_when_routes_tie() {
+        let scorers: Vec<Box<dyn RouteScorer>> = vec![
+            Box::new(ScrollScorer::default()),
+            Box::new(WeightedScorer {
+                dual_power_scrolls: 0.5,
+                cursed_chest_chance: -0.01,
+                ..WeightedScorer::default()
+            }),
+        ];
+        let mut ties = 0;
+
+        for seed in 0..20 {
+            let biomes = generated_biomes(seed, 6, 3);
+            assert_eq!(validation::validate(&biomes), vec![]);
+            for scorer in &scorers {
+                let brute_force = get_top_routes(
+                    Blacklist::default(),
+                    &0.into(),
+                    &Constraints::default(),
+                    scorer.as_ref(),
+                    2,
+                    Some(biomes.clone()),
+                )
+                .unwrap();
+                let best = get_best_route(
+                    Blacklist::default(),
+                    &0.into(),
+                    &Constraints::default(),
+                    scorer.as_ref(),
+                    Some(biomes.clone()),
+                )
+                .unwrap();
+
+                assert_eq!(best, brute_force[0], "seed: {}", seed);
+                if brute_force.len() > 1
+                    && compare_scores(brute_force[0].score, brute_force[1].score)
+                        == std::cmp::Ordering::Equal
+                {
+                    ties += 1;
+                }
+            }
+        }
+        assert!(ties > 0, "no dataset had tied routes");
+    }
+
+    #[test]
+    fn best_route_should_meet_constraints() {
+        let exit = |from: Id, to: Id| ExitId { from, to };
+        let constraints = vec![
+            Constraints {
+                required_biomes: vec![Id::Ossuary],
+                ..Constraints::default()
+            },
+            Constraints {
+                required_biomes: vec![Id::Graveyard, Id::Toxicsewers],
+                forbidden_exits: vec![exit(Id::Haven, Id::Throne)],
+                ..Constraints::default()
+            },
+            Constraints {
+                required_exits: vec![exit(Id::Promenade, Id::Ramparts)],
+                forbidden_exits: vec![exit(Id::Bridge, Id::Slumbering)],
+                ..Constraints::default()
+            },
+        ];
+        let scorer = ScrollScorer::default();
+
+        for constraints in &constraints {
+            let brute_force =
+                get_top_routes(vec![].into(), &5.into(), constraints, &scorer, 1, None).unwrap();
+            let best =
+                get_best_route(vec![].into(), &5.into(), constraints, &scorer, None).unwrap();
+
+            assert_eq!(best, brute_force[0], "{:?}", constraints);
+            assert_ne!(
+                best,
+                get_best_route(
+                    vec![].into(),
+                    &5.into(),
+                    &Constraints::default(),
+                    &scorer,
+                    None
+                )
+                .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn should_name_the_constraint_that_cant_be_met() {
+        let constraints = Constraints {
+            required_biomes: vec![Id::Ossuary, Id::Toxicsewers, Id::Cavern],
+            ..Constraints::default()
+        };
+        let expected = Error::NoRoute(
+            "No route from prisonquart to throne or observatory through toxicsewers".to_string(),
+        );
+        let scorer = ScrollScorer::default();
+
+        assert_eq!(
+            get_best_route(vec![].into(), &5.into(), &constraints, &scorer, None).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            get_top_routes(vec![].into(), &5.into(), &constraints, &scorer, 10, None).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            get_biomes_and_paths(vec![].into(), &5.into(), &constraints, None).unwrap_err(),
+            expected
+        );
+        // a blacklist that rules out every route is not a constraint problem
+        assert_eq!(
+            get_best_route(
+                vec![Id::Haven, Id::Castle].into(),
+                &5.into(),
+                &Constraints::default(),
+                &scorer,
+                None
+            ),
+            Err(Error::NoRoute(
+                "No route from prisonquart to throne or observatory".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_explain_why_biomes_are_disabled() {
+        let (biomes, paths) = get_biomes_and_paths(
+            vec![Id::Toxicsewers].into(),
+            &4.into(),
+            &Constraints::default(),
+            None,
+        )
+        .unwrap();
+        let reasons = |id: Id| -> Vec<DisabledReason> {
+            biomes
+                .iter()
+                .flatten()
+                .find(|biome| biome.id == id)
+                .unwrap()
+                .disabled_reasons
+                .clone()
+        };
+
+        assert_eq!(reasons(Id::Promenade), vec![]);
+        assert_eq!(reasons(Id::Toxicsewers), vec![DisabledReason::Blacklisted]);
+        assert_eq!(
+            reasons(Id::Corruptedprison),
+            vec![DisabledReason::BlacklistedRoute {
+                biomes: vec![Id::Toxicsewers],
+                exits: vec![],
+            }]
+        );
+        assert_eq!(
+            reasons(Id::Lab),
+            vec![DisabledReason::BossCells { required: 5 }]
+        );
+        let throne_lab = paths.iter().find(|path| path.id == "throne-lab").unwrap();
+        assert_eq!(
+            throne_lab.disabled_reasons,
+            vec![DisabledReason::BossCells { required: 5 }]
+        );
+    }
+
+    #[test]
+    fn biome_map_should_have_the_reachable_biomes_and_the_best_route() {
+        let scorer = ScrollScorer::default();
+        let map = get_biome_map(
+            vec![Id::Toxicsewers].into(),
+            &4.into(),
+            &Constraints::default(),
+            Some(&scorer),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(map.tiers[0][0].id, Id::Prisonquart);
+        assert!(map.reachable.contains(&Id::Promenade));
+        assert!(!map.reachable.contains(&Id::Toxicsewers));
+        assert!(!map.reachable.contains(&Id::Lab));
+        let best_route = map.best_route.as_ref().unwrap();
+        assert!(best_route.route.iter().all(|id| map.reachable.contains(id)));
+
+        let map = get_biome_map(
+            Blacklist::default(),
+            &4.into(),
+            &Constraints::default(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(map.best_route, None);
+        assert!(!map.paths.is_empty());
+
+        let scorer = ScrollScorer::default();
+        let map = get_biome_map(
+            vec![Id::Throne, Id::Lab].into(),
+            &5.into(),
+            &Constraints::default(),
+            Some(&scorer),
+            None,
+        )
+        .unwrap();
+        assert_eq!(map.best_route, None);
+        assert!(map.paths.iter().all(|path| !path.enabled));
+
+        let constraints = Constraints {
+            start: Some(Id::new("distillery")),
+            ..Constraints::default()
+        };
+        assert!(matches!(
+            get_biome_map(
+                Blacklist::default(),
+                &5.into(),
+                &constraints,
+                Some(&scorer),
+                None
+            ),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn biome_map_should_keep_its_serialized_field_names() {
+        let keys = |value: &serde_json::Value| -> Vec<String> {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        let scorer = ScrollScorer::default();
+        let map = get_biome_map(
+            Blacklist::default(),
+            &5.into(),
+            &Constraints::default(),
+            Some(&scorer),
+            None,
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&map).unwrap();
+        assert_eq!(
+            keys(&json),
+            vec!["best_route", "paths", "reachable", "tiers"]
+        );
+        assert_eq!(
+            keys(&json["best_route"]),
+            vec![
+                "behind_best",
+                "collectibles",
+                "cursed_chests",
+                "route",
+                "score"
+            ]
+        );
+        assert_eq!(json["reachable"][0], "prisonquart");
+        assert_eq!(json["best_route"]["route"][0], "prisonquart");
+        assert_eq!(json["best_route"]["behind_best"], 0.0);
+
+        let map = get_biome_map(
+            Blacklist::default(),
+            &5.into(),
+            &Constraints::default(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&map).unwrap()["best_route"],
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn should_honour_blacklisted_exits() {
+        let blacklist =
+            Blacklist::from_entries(vec!["prisonquart-promenade", "prisonquart-toxicsewers"]);
+        let constraints = Constraints::default();
+        let scorer = ScrollScorer::default();
+
+        let (biomes, paths) =
+            get_biomes_and_paths(blacklist.clone(), &5.into(), &constraints, None).unwrap();
+        let biome = |id: Id| {
+            biomes
+                .iter()
+                .flatten()
+                .find(|biome| biome.id == id)
+                .unwrap()
+        };
+        let path = |id: &str| paths.iter().find(|path| path.id == id).unwrap();
+        assert!(!path("prisonquart-promenade").enabled);
+        assert_eq!(
+            path("prisonquart-promenade").disabled_reasons,
+            vec![DisabledReason::Blacklisted]
+        );
+        assert!(path("prisonquart-arboretum").enabled);
+        assert!(biome(Id::Ramparts).enabled);
+        assert_eq!(
+            biome(Id::Promenade).disabled_reasons,
+            vec![DisabledReason::BlacklistedRoute {
+                biomes: vec![],
+                exits: vec![ExitId {
+                    from: Id::Prisonquart,
+                    to: Id::Promenade
+                }],
+            }]
+        );
+
+        let best =
+            get_best_route(blacklist.clone(), &5.into(), &constraints, &scorer, None).unwrap();
+        let top =
+            get_top_routes(blacklist.clone(), &5.into(), &constraints, &scorer, 1, None).unwrap();
+        assert_eq!(best.route[1], Id::Arboretum);
+        assert_eq!(top[0].route, best.route);
+
+        let minimum = get_minimum_boss_cells(blacklist, &5.into(), &constraints, None).unwrap();
+        let promenade = minimum.biomes.iter().find(|b| b.id == Id::Promenade);
+        assert_eq!(promenade.unwrap().boss_cells, None);
+    }
+
+    #[test]
+    fn should_route_past_the_throne_room_with_5_boss_cells() {
+        let enabled = |boss_cells: u8| -> Vec<Id> {
+            let (biomes, _) = get_biomes_and_paths(
+                vec![].into(),
+                &boss_cells.into(),
+                &Constraints::default(),
+                None,
+            )
+            .unwrap();
+            biomes
+                .into_iter()
+                .flatten()
+                .filter(|biome| biome.enabled)
+                .map(|biome| biome.id)
+                .collect()
+        };
+        assert!(!enabled(4).contains(&Id::new("observatory")));
+        assert!(enabled(5).contains(&Id::new("lab")));
+        assert!(enabled(5).contains(&Id::new("observatory")));
+
+        let dual_scrolls = ScrollScorer {
+            include_dual_scrolls: true,
+        };
+        let best = get_best_route(
+            vec![].into(),
+            &5.into(),
+            &Constraints::default(),
+            &dual_scrolls,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            &best.route[best.route.len() - 3..],
+            &[Id::Throne, Id::new("lab"), Id::new("observatory")]
+        );
+        let best = get_best_route(
+            vec![].into(),
+            &4.into(),
+            &Constraints::default(),
+            &dual_scrolls,
+            None,
+        )
+        .unwrap();
+        assert_eq!(best.route.last(), Some(&Id::Throne));
+    }
+
+    #[test]
+    fn should_route_between_any_biomes() {
+        let scorer = ScrollScorer::default();
+        for (start, end) in [
+            (Id::Promenade, Id::Cavern),
+            (Id::Toxicsewers, Id::Castle),
+            (Id::Ramparts, Id::new("observatory")),
+        ] {
+            let constraints = Constraints {
+                start: Some(start.clone()),
+                end: Some(end.clone()),
+                ..Constraints::default()
+            };
+            let top =
+                get_top_routes(vec![].into(), &5.into(), &constraints, &scorer, 1, None).unwrap();
+            let best =
+                get_best_route(vec![].into(), &5.into(), &constraints, &scorer, None).unwrap();
+
+            assert_eq!(best, top[0]);
+            assert_eq!(best.route.first(), Some(&start));
+            assert_eq!(best.route.last(), Some(&end));
+        }
+
+        let backwards = Constraints {
+            start: Some(Id::Cavern),
+            end: Some(Id::Promenade),
+            ..Constraints::default()
+        };
+        assert_eq!(
+            get_best_route(vec![].into(), &5.into(), &backwards, &scorer, None),
+            Err(Error::NoRoute(
+                "No route from cavern to promenade".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_only_simulate_real_routes() {
+        let route = vec![Id::Prisonquart, Id::Promenade, Id::Ossuary];
+        assert!(simulate_route(&route, 0, false, 10, 1, None).is_ok());
+        assert_eq!(
+            simulate_route(&route, 0, false, 0, 1, None),
+            Err(Error::InvalidInput("Can't simulate 0 runs".to_string()))
+        );
+        assert_eq!(
+            simulate_route(&[Id::Prisonquart, Id::Ossuary], 0, false, 10, 1, None),
+            Err(Error::InvalidInput(
+                "No exit from prisonquart to ossuary".to_string()
+            ))
+        );
+        assert_eq!(
+            simulate_route(&[Id::new("distillery")], 0, false, 10, 1, None),
+            Err(Error::InvalidInput(
+                "No biome with id distillery".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_count_scrolls_from_transitions() {
+        let biomes = get_biomes().unwrap();
+        let path: Vec<&Biome> = [
+            "prisonquart",
+            "arboretum",
+            "prisondepths",
+            "ancientsewers",
+            "crypt",
+            "slumbering",
+            "cavern",
+            "haven",
+            "throne",
+        ]
+        .iter()
+        .map(|id| biomes.iter().find(|biome| biome.id == Id::new(id)).unwrap())
+        .collect();
+
+        let collectibles = Collectibles::for_path(&path, 5);
+
+        assert_eq!(
+            collectibles.totals,
+            RouteTotals {
+                power_scrolls: 12,
+                dual_power_scrolls: 7,
+                scroll_fragments: 24,
+                cursed_chest_chance: 241,
+                transition_scrolls: 2,
+                gear_level: collectibles.totals.gear_level,
+            }
+        );
+        assert_eq!(collectibles.fragment_conversions, 6);
+        assert_eq!(collectibles.leftover_fragments, 0);
+        let throne = collectibles.line_items.last().unwrap();
+        assert_eq!(throne.biome, Id::Throne);
+        assert_eq!(throne.transition_scrolls, 2);
+        assert_eq!(
+            ScrollScorer::default().score(&RouteTotals::of(&path, 5)),
+            22.41
+        );
+    }
+
+    #[test]
+    fn weights_should_change_the_ranking() {
+        let scrolls = get_best_route(
+            vec![].into(),
+            &5.into(),
+            &Constraints::default(),
+            &ScrollScorer::default(),
+            None,
+        )
+        .unwrap();
+        let avoid_cursed_chests = WeightedScorer {
+            cursed_chest_chance: -1.,
+            ..WeightedScorer::default()
+        };
+        let safe = get_best_route(
+            vec![].into(),
+            &5.into(),
+            &Constraints::default(),
+            &avoid_cursed_chests,
+            None,
+        )
+        .unwrap();
+
+        assert_ne!(safe.route, scrolls.route);
+        assert!(
+            safe.collectibles.totals.cursed_chest_chance
+                < scrolls.collectibles.totals.cursed_chest_chance
+        );
+    }
+
+    #[test]
+    fn test_order_biomes_by_row() {
+        let biomes = vec![
+            (Id::Prisonquart, 1, 1).into(),
+            (Id::Promenade, 2, 1).into(),
+            (Id::Toxicsewers, 2, 2).into(),
+        ];
+
+        let result = order_biomes_by_tier(biomes);
+        let result = result.unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                vec![(Id::Prisonquart, 1, 1).into()],
+                vec![(Id::Promenade, 2, 1).into(), (Id::Toxicsewers, 2, 2).into()]
+            ]
+        );
+    }
+
+    fn path_to_names<'b>(path: &Vec<&'b Biome>) -> Vec<&'b String> {
+        path.iter().map(|biome| &biome.name).collect()
+    }
+
+    fn path_to_ids<'b>(path: &Vec<&'b Biome>) -> Vec<&'b Id> {
+        path.iter().map(|biome| &biome.id).collect()
+    }
+
+    impl From<(Id, Vec<Id>)> for Biome {
+        fn from((id, exits): (Id, Vec<Id>)) -> Self {
+            let name = id.to_string();
+            let exits = exits.into_iter().map(Exit::from).collect();
+            Biome {
+                id,
+                name,
+                row: 0,
+                column: 0,
+                power_scrolls: 0,
+                dual_power_scrolls: 0,
+                cursed_chest_chance: 0,
+                scroll_fragments: ScrollFragments::default(),
+                gear_level: 0,
+                exits,
+                enabled: true,
+                disabled_reasons: vec![],
+            }
+        }
+    }
+
+    impl From<(Id, usize, usize)> for Biome {
+        fn from((id, row, column): (Id, usize, usize)) -> Self {
+            let name = id.to_string();
+            Biome {
+                id,
+                name,
+                row,
+                column,
+                power_scrolls: 0,
+                dual_power_scrolls: 0,
+                cursed_chest_chance: 0,
+                scroll_fragments: ScrollFragments::default(),
+                gear_level: 0,
+                exits: vec![],
+                enabled: true,
+                disabled_reasons: vec![],
+            }
+        }
+    }
+
+    impl From<(Id, usize, usize, Vec<Id>, bool)> for Biome {
+        fn from((id, row, column, exits, enabled): (Id, usize, usize, Vec<Id>, bool)) -> Self {
+            let name = id.to_string();
+            let exits = exits.into_iter().map(Exit::from).collect();
+            Biome {
+                id,
+                name,
+                row,
+                column,
+                power_scrolls: 0,
+                dual_power_scrolls: 0,
+                cursed_chest_chance: 0,
+                scroll_fragments: ScrollFragments::default(),
+                gear_level: 0,
+                exits,
+                enabled,
+                disabled_reasons: vec![],
+            }
+        }
+    }
+}
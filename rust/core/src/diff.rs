@@ -0,0 +1,242 @@
+use crate::collectibles;
+use crate::json::models::*;
+use crate::scoring::RouteTotals;
+use serde::Serialize;
+
+/// How a second route compares to a first one
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct RouteDiff {
+    /// the stretches where the routes go through different biomes, in route order
+    pub segments: Vec<Segment>,
+    /// the second route's collectibles minus the first route's
+    pub delta: Delta,
+}
+
+/// A stretch where the two routes part ways
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct Segment {
+    /// the last biome both routes go through before they part, none if they start apart
+    pub diverges_after: Option<Id>,
+    /// the first biome both routes go through again, none if they end apart
+    pub merges_at: Option<Id>,
+    pub first: Vec<Id>,
+    pub second: Vec<Id>,
+    /// what the second route's stretch picks up minus what the first route's does, exits in and
+    /// out of the stretch included
+    pub delta: Delta,
+}
+
+/// The difference between two routes' collectibles, positive when the second route has more
+#[derive(Serialize, Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct Delta {
+    pub power_scrolls: i64,
+    pub dual_power_scrolls: i64,
+    pub scroll_fragments: i64,
+    /// scrolls made out of fragments
+    pub fragment_conversions: i64,
+    pub cursed_chest_chance: i64,
+    pub transition_scrolls: i64,
+    pub gear_level: i64,
+}
+
+impl Delta {
+    fn between(first: &RouteTotals, second: &RouteTotals) -> Self {
+        let delta = |first: u32, second: u32| second as i64 - first as i64;
+        Delta {
+            power_scrolls: delta(first.power_scrolls, second.power_scrolls),
+            dual_power_scrolls: delta(first.dual_power_scrolls, second.dual_power_scrolls),
+            scroll_fragments: delta(first.scroll_fragments, second.scroll_fragments),
+            fragment_conversions: delta(
+                first.scrolls_from_fragments(),
+                second.scrolls_from_fragments(),
+            ),
+            cursed_chest_chance: delta(first.cursed_chest_chance, second.cursed_chest_chance),
+            transition_scrolls: delta(first.transition_scrolls, second.transition_scrolls),
+            gear_level: delta(first.gear_level, second.gear_level),
+        }
+    }
+
+    fn minus(self, other: Delta) -> Self {
+        Delta {
+            power_scrolls: self.power_scrolls - other.power_scrolls,
+            dual_power_scrolls: self.dual_power_scrolls - other.dual_power_scrolls,
+            scroll_fragments: self.scroll_fragments - other.scroll_fragments,
+            fragment_conversions: self.fragment_conversions - other.fragment_conversions,
+            cursed_chest_chance: self.cursed_chest_chance - other.cursed_chest_chance,
+            transition_scrolls: self.transition_scrolls - other.transition_scrolls,
+            gear_level: self.gear_level - other.gear_level,
+        }
+    }
+}
+
+/// Lines the routes up biome by biome. Rows only ever go up, so two routes go through the same
+/// biomes in the same order and whichever route is behind by row is the one to move on.
+pub fn diff(first: &[&Biome], second: &[&Biome], boss_cells: u8) -> RouteDiff {
+    // running_totals[i] is what a route has picked up before its i-th biome
+    let running_totals = |path: &[&Biome]| {
+        let mut totals = vec![RouteTotals::default()];
+        totals.extend(
+            collectibles::explain(path, boss_cells)
+                .into_iter()
+                .map(|explanation| explanation.running_totals),
+        );
+        totals
+    };
+    let first_totals = running_totals(first);
+    let second_totals = running_totals(second);
+    // the difference up to and including the i-th biome of the first and the j-th of the second
+    let delta_through = |i: usize, j: usize| {
+        let i = (i + 1).min(first.len());
+        let j = (j + 1).min(second.len());
+        Delta::between(&first_totals[i], &second_totals[j])
+    };
+
+    let mut segments = vec![];
+    let (mut i, mut j) = (0, 0);
+    let (mut start_i, mut start_j) = (0, 0);
+    loop {
+        let at_end = i == first.len() && j == second.len();
+        let shared = i < first.len() && j < second.len() && first[i].id == second[j].id;
+        if at_end || shared {
+            if (i, j) != (start_i, start_j) {
+                let diverges_after = start_i.checked_sub(1).map(|i| first[i].id.clone());
+                let before = match diverges_after {
+                    Some(_) => delta_through(start_i - 1, start_j - 1),
+                    None => Delta::default(),
+                };
+                segments.push(Segment {
+                    diverges_after,
+                    merges_at: first.get(i).map(|biome| biome.id.clone()),
+                    first: ids(&first[start_i..i]),
+                    second: ids(&second[start_j..j]),
+                    delta: delta_through(i, j).minus(before),
+                });
+            }
+            if at_end {
+                break;
+            }
+            i += 1;
+            j += 1;
+            start_i = i;
+            start_j = j;
+            continue;
+        }
+        match (first.get(i), second.get(j)) {
+            (Some(left), Some(right)) if left.row < right.row => i += 1,
+            (Some(left), Some(right)) if left.row > right.row => j += 1,
+            (Some(_), Some(_)) => {
+                i += 1;
+                j += 1;
+            }
+            (Some(_), None) => i += 1,
+            (None, _) => j += 1,
+        }
+    }
+
+    RouteDiff {
+        segments,
+        delta: Delta::between(&first_totals[first.len()], &second_totals[second.len()]),
+    }
+}
+
+fn ids(path: &[&Biome]) -> Vec<Id> {
+    path.iter().map(|biome| biome.id.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+
+    fn route<'b>(biomes: &'b [Biome], ids: &[&str]) -> Vec<&'b Biome> {
+        ids.iter()
+            .map(|id| biomes.iter().find(|biome| biome.id == Id::new(id)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_find_where_routes_diverge_and_merge() {
+        let biomes = core::get_biomes().unwrap();
+        let first = route(
+            &biomes,
+            &[
+                "prisonquart",
+                "promenade",
+                "ossuary",
+                "bridge",
+                "slumbering",
+                "cavern",
+                "haven",
+                "throne",
+            ],
+        );
+        let second = route(
+            &biomes,
+            &[
+                "prisonquart",
+                "arboretum",
+                "prisondepths",
+                "ossuary",
+                "bridge",
+                "slumbering",
+                "sepulcher",
+                "haven",
+                "throne",
+            ],
+        );
+
+        let diff = diff(&first, &second, 5);
+
+        assert_eq!(diff.segments.len(), 2);
+        let segment = &diff.segments[0];
+        assert_eq!(segment.diverges_after, Some(Id::Prisonquart));
+        assert_eq!(segment.merges_at, Some(Id::Ossuary));
+        assert_eq!(segment.first, vec![Id::Promenade]);
+        assert_eq!(segment.second, vec![Id::Arboretum, Id::Prisondepths]);
+        let total = |path: &[&Biome]| RouteTotals::of(path, 5);
+        let expected = Delta::between(&total(&first[1..2]), &total(&second[1..3]));
+        assert_eq!(segment.delta.power_scrolls, expected.power_scrolls);
+        assert_eq!(segment.delta.gear_level, expected.gear_level);
+        let segment = &diff.segments[1];
+        assert_eq!(segment.diverges_after, Some(Id::Slumbering));
+        assert_eq!(segment.merges_at, Some(Id::Haven));
+        assert_eq!(segment.first, vec![Id::Cavern]);
+        assert_eq!(segment.second, vec![Id::Sepulcher]);
+
+        let summed = diff.segments.iter().fold(0, |sum, segment| {
+            sum + segment.delta.power_scrolls + segment.delta.cursed_chest_chance
+        });
+        assert_eq!(
+            summed,
+            diff.delta.power_scrolls + diff.delta.cursed_chest_chance
+        );
+    }
+
+    #[test]
+    fn should_diff_routes_that_end_apart() {
+        let biomes = core::get_biomes().unwrap();
+        let first = route(&biomes, &["prisonquart", "promenade", "ossuary"]);
+        let second = route(&biomes, &["prisonquart", "promenade", "prisondepths"]);
+
+        let diff = diff(&first, &second, 0);
+
+        assert_eq!(diff.segments.len(), 1);
+        let segment = &diff.segments[0];
+        assert_eq!(segment.diverges_after, Some(Id::Promenade));
+        assert_eq!(segment.merges_at, None);
+        assert_eq!(segment.first, vec![Id::Ossuary]);
+        assert_eq!(segment.second, vec![Id::Prisondepths]);
+        assert_eq!(segment.delta, diff.delta);
+    }
+
+    #[test]
+    fn the_same_route_should_not_differ() {
+        let biomes = core::get_biomes().unwrap();
+        let path = route(&biomes, &["prisonquart", "promenade", "ossuary"]);
+
+        let diff = diff(&path, &path, 3);
+
+        assert!(diff.segments.is_empty());
+        assert_eq!(diff.delta, Delta::default());
+    }
+}
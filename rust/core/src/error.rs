@@ -0,0 +1,52 @@
+use serde::Serialize;
+use std::fmt;
+
+/// Why the planner couldn't answer, goes to JS as `{code: "no_route", message: "..."}`
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum Error {
+    /// an argument that makes no sense, e.g. 7 boss cells or a biome that isn't in the dataset
+    InvalidInput(String),
+    /// a dataset that doesn't parse or doesn't pass validation
+    InvalidDataset(String),
+    /// the blacklist, progress and constraints leave no route
+    NoRoute(String),
+    /// a bug, e.g. a result that can't be serialized
+    Internal(String),
+}
+
+impl Error {
+    pub fn message(&self) -> &str {
+        match self {
+            Error::InvalidInput(message)
+            | Error::InvalidDataset(message)
+            | Error::NoRoute(message)
+            | Error::Internal(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_to_code_and_message() {
+        let error = Error::NoRoute("No route from cavern to promenade".to_string());
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "no_route",
+                "message": "No route from cavern to promenade",
+            })
+        );
+        assert_eq!(error.to_string(), "No route from cavern to promenade");
+    }
+}
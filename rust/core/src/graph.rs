@@ -0,0 +1,275 @@
+use crate::blacklist::Blacklist;
+use crate::constraints::Constraints;
+use crate::error::Error;
+use crate::json::models::*;
+use crate::scoring::{compare_scores, RouteScorer, RouteTotals};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// The biomes as a DAG, every exit leads to a higher row so ordering biomes by row is a
+/// topological order
+pub struct Graph<'b> {
+    pub biomes: &'b [Biome],
+    /// biome indexes sorted by row
+    order: Vec<usize>,
+    /// outgoing edges per biome index, as (destination index, exit)
+    edges: Vec<Vec<(usize, &'b Exit)>>,
+    index: HashMap<&'b Id, usize>,
+}
+
+impl<'b> Graph<'b> {
+    pub fn new(biomes: &'b [Biome]) -> Result<Self, Error> {
+        let index: HashMap<&Id, usize> = biomes
+            .iter()
+            .enumerate()
+            .map(|(i, biome)| (&biome.id, i))
+            .collect();
+
+        let mut edges = vec![];
+        for biome in biomes {
+            let mut exits = vec![];
+            for exit in &biome.exits {
+                let destination = *index.get(&exit.destination).ok_or_else(|| {
+                    Error::InvalidDataset(format!("No biome with id {}", exit.destination))
+                })?;
+                if biomes[destination].row <= biome.row {
+                    return Err(Error::InvalidDataset(format!(
+                        "Exit from {} to {} doesn't lead to a higher row",
+                        biome.id, exit.destination
+                    )));
+                }
+                exits.push((destination, exit));
+            }
+            edges.push(exits);
+        }
+
+        let mut order: Vec<usize> = (0..biomes.len()).collect();
+        order.sort_by_key(|i| biomes[*i].row);
+
+        Ok(Graph {
+            biomes,
+            order,
+            edges,
+            index,
+        })
+    }
+
+    /// The graph build.rs laid out for the compiled-in biomes, `edges` has the destination index
+    /// of every exit
+    pub(crate) fn precomputed(biomes: &'b [Biome], order: &[usize], edges: &[&[usize]]) -> Self {
+        Graph {
+            biomes,
+            order: order.to_vec(),
+            edges: biomes
+                .iter()
+                .zip(edges)
+                .map(|(biome, destinations)| {
+                    destinations.iter().cloned().zip(&biome.exits).collect()
+                })
+                .collect(),
+            index: biomes
+                .iter()
+                .enumerate()
+                .map(|(i, biome)| (&biome.id, i))
+                .collect(),
+        }
+    }
+
+    pub fn index_of(&self, id: &Id) -> Option<usize> {
+        self.index.get(id).cloned()
+    }
+
+    pub fn edges(&self, biome: usize) -> &[(usize, &'b Exit)] {
+        &self.edges[biome]
+    }
+
+    /// Biome indexes in topological order
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Whether the exit of `biome` can be taken and neither it nor where it leads is blacklisted
+    pub fn is_open(
+        &self,
+        biome: usize,
+        exit: &Exit,
+        blacklist: &Blacklist,
+        progress: &Progress,
+    ) -> bool {
+        blacklist.allows_exit(&self.biomes[biome].id, &exit.destination)
+            && exit
+                .all_requirements()
+                .iter()
+                .all(|requirement| progress.satisfies(requirement))
+    }
+
+    /// The edges of `biome` a route can take given the blacklist, progress and constraints,
+    /// `required` is what `Constraints::required` returned for the graph's biomes
+    pub fn open_edges(
+        &self,
+        biome: usize,
+        blacklist: &Blacklist,
+        progress: &Progress,
+        constraints: &Constraints,
+        required: &[&Biome],
+    ) -> Vec<(usize, &'b Exit)> {
+        self.edges(biome)
+            .iter()
+            .filter(|(destination, exit)| {
+                self.is_open(biome, exit, blacklist, progress)
+                    && constraints.allows_exit_past(
+                        required,
+                        &self.biomes[biome],
+                        &self.biomes[*destination],
+                    )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The indexes of `ends` routes can end in given the constraints
+    pub fn allowed_ends(&self, ends: &[Id], constraints: &Constraints) -> Vec<usize> {
+        ends.iter()
+            .filter_map(|end| self.index_of(end))
+            .filter(|end| constraints.allows_end(self.biomes, &self.biomes[*end]))
+            .collect()
+    }
+}
+
+/// Fragments turn into a scroll every 4, so the only thing two route prefixes ending in the
+/// same biome can differ in, as far as the rest of the route is concerned, is the remainder
+const STATES: usize = 4;
+
+#[derive(Clone, Copy)]
+struct Label {
+    totals: RouteTotals,
+    previous: Option<(usize, usize)>,
+}
+
+impl Label {
+    fn state(&self) -> usize {
+        self.totals.scroll_fragments as usize % STATES
+    }
+}
+
+/// Finds the route from `start` to any of the `ends` that `scorer` likes best, ties are broken
+/// by the lowest biome ids. Runs in O(edges * 4) since the fragment remainder is the only state
+/// that needs tracking, see `RouteScorer` for what that asks of the scorer.
+pub(crate) fn best_route<'b>(
+    graph: &Graph<'b>,
+    start: &Id,
+    ends: &[Id],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    scorer: &dyn RouteScorer,
+) -> Option<(f64, Vec<&'b Biome>)> {
+    let start = graph.index_of(start)?;
+    if blacklist.contains_biome(&graph.biomes[start].id)
+        || !constraints.allows_start(graph.biomes, &graph.biomes[start])
+    {
+        return None;
+    }
+    let ends = graph.allowed_ends(ends, constraints);
+    let required = constraints.required(graph.biomes);
+
+    let mut labels: Vec<Vec<Option<Label>>> = vec![vec![None; STATES]; graph.biomes.len()];
+    let mut totals = RouteTotals::default();
+    totals.add_biome(&graph.biomes[start], progress.boss_cells);
+    let label = Label {
+        totals,
+        previous: None,
+    };
+    labels[start][label.state()] = Some(label);
+
+    for &biome in graph.order() {
+        let edges = graph.open_edges(biome, blacklist, progress, constraints, &required);
+        for state in 0..STATES {
+            let label = match labels[biome][state] {
+                Some(label) => label,
+                None => continue,
+            };
+            for &(destination, exit) in &edges {
+                let mut totals = label.totals;
+                totals.add_exit(exit);
+                totals.add_biome(&graph.biomes[destination], progress.boss_cells);
+                let candidate = Label {
+                    totals,
+                    previous: Some((biome, state)),
+                };
+                let next_state = candidate.state();
+                let better = match labels[destination][next_state] {
+                    None => true,
+                    Some(existing) => {
+                        let candidate_score = scorer.score(&candidate.totals);
+                        let existing_score = scorer.score(&existing.totals);
+                        match compare_scores(candidate_score, existing_score) {
+                            Ordering::Greater => true,
+                            Ordering::Less => false,
+                            Ordering::Equal => {
+                                let mut candidate_ids = route_ids(graph, &labels, (biome, state));
+                                candidate_ids.push(&graph.biomes[destination].id);
+                                candidate_ids < route_ids(graph, &labels, (destination, next_state))
+                            }
+                        }
+                    }
+                };
+                if better {
+                    labels[destination][next_state] = Some(candidate);
+                }
+            }
+        }
+    }
+
+    let mut best: Option<(f64, (usize, usize))> = None;
+    for &end in &ends {
+        for state in 0..STATES {
+            if let Some(label) = labels[end][state] {
+                let score = scorer.score(&label.totals);
+                let better = match best {
+                    None => true,
+                    Some((best_score, best_end)) => match compare_scores(score, best_score) {
+                        Ordering::Greater => true,
+                        Ordering::Less => false,
+                        Ordering::Equal => {
+                            route_ids(graph, &labels, (end, state))
+                                < route_ids(graph, &labels, best_end)
+                        }
+                    },
+                };
+                if better {
+                    best = Some((score, (end, state)));
+                }
+            }
+        }
+    }
+
+    let (score, (end, state)) = best?;
+    let route = route_indexes(&labels, (end, state))
+        .into_iter()
+        .map(|i| &graph.biomes[i])
+        .collect();
+    Some((score, route))
+}
+
+fn route_indexes(labels: &[Vec<Option<Label>>], (biome, state): (usize, usize)) -> Vec<usize> {
+    let mut route = vec![biome];
+    let mut previous = labels[biome][state].and_then(|label| label.previous);
+    while let Some((biome, state)) = previous {
+        route.push(biome);
+        previous = labels[biome][state].and_then(|label| label.previous);
+    }
+    route.reverse();
+    route
+}
+
+fn route_ids<'b>(
+    graph: &Graph<'b>,
+    labels: &[Vec<Option<Label>>],
+    last: (usize, usize),
+) -> Vec<&'b Id> {
+    route_indexes(labels, last)
+        .into_iter()
+        .map(|i| &graph.biomes[i].id)
+        .collect()
+}
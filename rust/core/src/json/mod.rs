@@ -0,0 +1,7 @@
+pub mod models;
+pub mod validation;
+
+/// The biomes.json that is compiled in, the dataset used when none is given
+pub(crate) fn get_json() -> &'static str {
+    include_str!("biomes.json")
+}
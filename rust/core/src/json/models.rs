@@ -0,0 +1,347 @@
+use crate::constraints::ExitId;
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+// todo remove clone and use borrowed biomes everywhere
+#[serde(deny_unknown_fields)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Biome {
+    pub id: Id,
+    pub name: String,
+    pub row: usize,
+    pub column: usize,
+    pub power_scrolls: u8,
+    pub dual_power_scrolls: u8,
+    pub cursed_chest_chance: u8,
+    pub scroll_fragments: ScrollFragments,
+    pub gear_level: u8,
+    pub exits: Vec<Exit>,
+    #[serde(skip_deserializing)]
+    pub enabled: bool,
+    /// why the biome is disabled, empty if it isn't
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_reasons: Vec<DisabledReason>,
+}
+
+impl Biome {
+    /// Whether a run can end here, that is in the Throne Room or, for runs that go past it, in a
+    /// biome there's no exit from like the Observatory
+    pub fn is_final(&self) -> bool {
+        self.id == Id::Throne || self.exits.is_empty()
+    }
+}
+
+/// Biome identifier as written in the dataset.
+/// The base game's ids are static strs, any other id shares its str between clones and is freed
+/// with the last of them
+#[derive(Clone)]
+pub struct Id(IdStr);
+
+#[derive(Clone)]
+enum IdStr {
+    Static(&'static str),
+    Shared(Arc<str>),
+}
+
+impl Id {
+    pub fn new(id: &str) -> Self {
+        match Id::BASE_GAME.iter().find(|known| known.as_str() == id) {
+            Some(known) => known.clone(),
+            None => Id(IdStr::Shared(Arc::from(id))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            IdStr::Static(id) => id,
+            IdStr::Shared(id) => id,
+        }
+    }
+}
+
+/// The biomes of the base game, handy in code and tests but datasets are free to use any other id
+#[allow(non_upper_case_globals)]
+impl Id {
+    pub const Prisonquart: Id = Id(IdStr::Static("prisonquart"));
+    pub const Arboretum: Id = Id(IdStr::Static("arboretum"));
+    pub const Promenade: Id = Id(IdStr::Static("promenade"));
+    pub const Toxicsewers: Id = Id(IdStr::Static("toxicsewers"));
+    pub const Prisondepths: Id = Id(IdStr::Static("prisondepths"));
+    pub const Corruptedprison: Id = Id(IdStr::Static("corruptedprison"));
+    pub const Morass: Id = Id(IdStr::Static("morass"));
+    pub const Ossuary: Id = Id(IdStr::Static("ossuary"));
+    pub const Ramparts: Id = Id(IdStr::Static("ramparts"));
+    pub const Ancientsewers: Id = Id(IdStr::Static("ancientsewers"));
+    pub const Nest: Id = Id(IdStr::Static("nest"));
+    pub const Bridge: Id = Id(IdStr::Static("bridge"));
+    pub const Crypt: Id = Id(IdStr::Static("crypt"));
+    pub const Stilt: Id = Id(IdStr::Static("stilt"));
+    pub const Slumbering: Id = Id(IdStr::Static("slumbering"));
+    pub const Graveyard: Id = Id(IdStr::Static("graveyard"));
+    pub const Clocktower: Id = Id(IdStr::Static("clocktower"));
+    pub const Sepulcher: Id = Id(IdStr::Static("sepulcher"));
+    pub const Cavern: Id = Id(IdStr::Static("cavern"));
+    pub const Clockroom: Id = Id(IdStr::Static("clockroom"));
+    pub const Haven: Id = Id(IdStr::Static("haven"));
+    pub const Castle: Id = Id(IdStr::Static("castle"));
+    pub const Throne: Id = Id(IdStr::Static("throne"));
+    pub const Lab: Id = Id(IdStr::Static("lab"));
+    pub const Observatory: Id = Id(IdStr::Static("observatory"));
+
+    const BASE_GAME: &'static [Id] = &[
+        Id::Prisonquart,
+        Id::Arboretum,
+        Id::Promenade,
+        Id::Toxicsewers,
+        Id::Prisondepths,
+        Id::Corruptedprison,
+        Id::Morass,
+        Id::Ossuary,
+        Id::Ramparts,
+        Id::Ancientsewers,
+        Id::Nest,
+        Id::Bridge,
+        Id::Crypt,
+        Id::Stilt,
+        Id::Slumbering,
+        Id::Graveyard,
+        Id::Clocktower,
+        Id::Sepulcher,
+        Id::Cavern,
+        Id::Clockroom,
+        Id::Haven,
+        Id::Castle,
+        Id::Throne,
+        Id::Lab,
+        Id::Observatory,
+    ];
+}
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Id {}
+
+impl Hash for Id {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Id {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl From<&str> for Id {
+    fn from(id: &str) -> Self {
+        Id::new(id)
+    }
+}
+
+impl Serialize for Id {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Id {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Id::new(id.as_str()))
+    }
+}
+
+impl fmt::Debug for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[serde(deny_unknown_fields)]
+#[derive(Serialize, Deserialize, Debug, Default, Eq, PartialEq, Clone)]
+pub struct ScrollFragments {
+    #[serde(alias = "0")]
+    pub zero: Option<u8>,
+    #[serde(alias = "1")]
+    pub one: Option<u8>,
+    #[serde(alias = "2")]
+    pub two: Option<u8>,
+    #[serde(alias = "3")]
+    pub three: Option<u8>,
+    #[serde(alias = "4")]
+    pub four: Option<u8>,
+    #[serde(alias = "5")]
+    pub five: Option<u8>,
+}
+
+impl ScrollFragments {
+    pub fn get_fragments(&self, boss_cells: u8) -> u8 {
+        let zero = self.zero;
+        let one = self.one.or(zero);
+        let two = self.two.or(one);
+        let three = self.three.or(two);
+        let four = self.four.or(three);
+        let five = self.five.or(four);
+
+        match boss_cells {
+            0 => zero.unwrap_or(0),
+            1 => one.unwrap_or(0),
+            2 => two.unwrap_or(0),
+            3 => three.unwrap_or(0),
+            4 => four.unwrap_or(0),
+            // there's no more than 5 boss cells, anything above gets what 5 do
+            _ => five.unwrap_or(0),
+        }
+    }
+}
+
+#[serde(deny_unknown_fields)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Exit {
+    pub destination: Id,
+    pub boss_cell_requirement: Option<u8>,
+    pub power_scrolls: Option<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requirements: Vec<Requirement>,
+}
+
+impl Exit {
+    /// Everything needed to take this exit, including the boss cell requirement
+    pub fn all_requirements(&self) -> Vec<Requirement> {
+        self.boss_cell_requirement
+            .map(Requirement::BossCells)
+            .into_iter()
+            .chain(self.requirements.iter().cloned())
+            .collect()
+    }
+}
+
+// todo remove?
+impl From<Id> for Exit {
+    fn from(destination: Id) -> Self {
+        Exit {
+            destination,
+            boss_cell_requirement: None,
+            power_scrolls: None,
+            requirements: vec![],
+        }
+    }
+}
+
+impl From<(Id, u8)> for Exit {
+    fn from((destination, boss_cell_requirement): (Id, u8)) -> Self {
+        Exit {
+            destination,
+            boss_cell_requirement: Some(boss_cell_requirement),
+            power_scrolls: None,
+            requirements: vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum Rune {
+    Vine,
+    Teleportation,
+    Ram,
+    Spider,
+    Challenger,
+    Explorer,
+}
+
+/// Something the player needs to have to take an exit, e.g. `{"rune": "ram"}` or `{"key": "gardener"}`
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Hash, Ord, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum Requirement {
+    BossCells(u8),
+    Rune(Rune),
+    Key(String),
+}
+
+/// Why a biome or path can't be part of a route, e.g. `{"reason": "boss_cells", "required": 3}`
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Ord, PartialOrd)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum DisabledReason {
+    /// the biome, the path or a biome at either end of it is blacklisted
+    Blacklisted,
+    /// every route through it goes through one of these blacklisted biomes or exits
+    BlacklistedRoute {
+        biomes: Vec<Id>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        exits: Vec<ExitId>,
+    },
+    /// every route through it needs more boss cells than that
+    BossCells { required: u8 },
+    /// no route from the start gets there, with the runes, keys and constraints given
+    Unreachable,
+}
+
+impl fmt::Display for DisabledReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisabledReason::Blacklisted => write!(f, "blacklisted"),
+            DisabledReason::BlacklistedRoute { biomes, exits } => {
+                let blacklisted: Vec<String> = biomes
+                    .iter()
+                    .map(|biome| biome.to_string())
+                    .chain(exits.iter().map(|exit| exit.to_string()))
+                    .collect();
+                write!(f, "every route goes through {}", blacklisted.join(" or "))
+            }
+            DisabledReason::BossCells { required } => write!(f, "needs {} boss cells", required),
+            DisabledReason::Unreachable => write!(f, "unreachable"),
+        }
+    }
+}
+
+/// What the player has unlocked so far
+#[derive(Serialize, Deserialize, Debug, Default, Eq, PartialEq, Clone)]
+pub struct Progress {
+    #[serde(default)]
+    pub boss_cells: u8,
+    #[serde(default)]
+    pub runes: Vec<Rune>,
+    #[serde(default)]
+    pub keys: Vec<String>,
+}
+
+impl Progress {
+    pub fn satisfies(&self, requirement: &Requirement) -> bool {
+        match requirement {
+            Requirement::BossCells(boss_cells) => self.boss_cells >= *boss_cells,
+            Requirement::Rune(rune) => self.runes.contains(rune),
+            Requirement::Key(key) => self.keys.contains(key),
+        }
+    }
+}
+
+impl From<u8> for Progress {
+    fn from(boss_cells: u8) -> Self {
+        Progress {
+            boss_cells,
+            ..Progress::default()
+        }
+    }
+}
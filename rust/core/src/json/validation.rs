@@ -0,0 +1,232 @@
+use crate::json::models::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Rows are laid out in a fixed amount of tiers, see `core::order_biomes_by_tier`
+pub const MAX_ROWS: usize = 14;
+pub const MAX_BOSS_CELLS: u8 = 5;
+
+/// A structural problem in a dataset, `field` is the path to the offending value inside the biome
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct ValidationError {
+    pub biome: Id,
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(biome: &Id, field: &str, message: String) -> Self {
+        ValidationError {
+            biome: biome.clone(),
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}: {}", self.biome, self.field, self.message)
+    }
+}
+
+/// Returns every structural problem in `biomes` that would otherwise surface as a panic
+/// somewhere in the planner, an empty vec means the dataset is fine
+pub fn validate(biomes: &[Biome]) -> Vec<ValidationError> {
+    let mut errors = vec![];
+
+    let mut ids: HashMap<&Id, &Biome> = HashMap::new();
+    let mut positions: HashMap<(usize, usize), &Id> = HashMap::new();
+    for biome in biomes {
+        if ids.insert(&biome.id, biome).is_some() {
+            errors.push(ValidationError::new(
+                &biome.id,
+                "id",
+                format!("duplicate biome id {}", biome.id),
+            ));
+        }
+        // blacklists and paths tell exits apart from biomes by the dash, see `Blacklist::from_entries`
+        if biome.id.to_string().contains('-') {
+            errors.push(ValidationError::new(
+                &biome.id,
+                "id",
+                format!("biome id {} has a dash in it", biome.id),
+            ));
+        }
+        if biome.row == 0 || biome.row > MAX_ROWS {
+            errors.push(ValidationError::new(
+                &biome.id,
+                "row",
+                format!("row {} is outside of 1..={}", biome.row, MAX_ROWS),
+            ));
+        }
+        if let Some(other) = positions.insert((biome.row, biome.column), &biome.id) {
+            errors.push(ValidationError::new(
+                &biome.id,
+                "column",
+                format!(
+                    "row {} column {} is already taken by {}",
+                    biome.row, biome.column, other
+                ),
+            ));
+        }
+    }
+
+    for biome in biomes {
+        for (i, exit) in biome.exits.iter().enumerate() {
+            let field = format!("exits[{}]", i);
+            if biome.exits[..i]
+                .iter()
+                .any(|other| other.destination == exit.destination)
+            {
+                errors.push(ValidationError::new(
+                    &biome.id,
+                    format!("{}.destination", field).as_str(),
+                    format!("duplicate exit to {}", exit.destination),
+                ));
+            }
+            match ids.get(&exit.destination) {
+                None => errors.push(ValidationError::new(
+                    &biome.id,
+                    format!("{}.destination", field).as_str(),
+                    format!("no biome with id {}", exit.destination),
+                )),
+                Some(destination) if destination.row <= biome.row => {
+                    errors.push(ValidationError::new(
+                        &biome.id,
+                        format!("{}.destination", field).as_str(),
+                        format!(
+                        "exit goes from row {} to {} at row {}, exits must lead to a higher row",
+                        biome.row, exit.destination, destination.row
+                    ),
+                    ))
+                }
+                Some(_) => {}
+            }
+            if let Some(boss_cells) = exit.boss_cell_requirement {
+                if boss_cells > MAX_BOSS_CELLS {
+                    errors.push(ValidationError::new(
+                        &biome.id,
+                        format!("{}.boss_cell_requirement", field).as_str(),
+                        format!(
+                            "requires {} boss cells but there are only {}",
+                            boss_cells, MAX_BOSS_CELLS
+                        ),
+                    ));
+                }
+            }
+            for (j, requirement) in exit.requirements.iter().enumerate() {
+                if let Requirement::BossCells(boss_cells) = requirement {
+                    if *boss_cells > MAX_BOSS_CELLS {
+                        errors.push(ValidationError::new(
+                            &biome.id,
+                            format!("{}.requirements[{}]", field, j).as_str(),
+                            format!(
+                                "requires {} boss cells but there are only {}",
+                                boss_cells, MAX_BOSS_CELLS
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if !ids.contains_key(&Id::Throne) {
+        errors.push(ValidationError::new(
+            &Id::Throne,
+            "id",
+            "the dataset has no throne room".to_string(),
+        ));
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+
+    #[test]
+    fn shipped_dataset_should_be_valid() {
+        let biomes = core::get_biomes().unwrap();
+
+        assert_eq!(validate(&biomes), vec![]);
+    }
+
+    #[test]
+    fn should_report_every_problem() {
+        let mut prisonquart = biome(Id::Prisonquart, 1, 1);
+        prisonquart.exits = vec![
+            Exit::from(Id::Promenade),
+            Exit::from(Id::Castle),
+            Exit::from(Id::Promenade),
+        ];
+        let mut promenade = biome(Id::Promenade, 2, 1);
+        promenade.exits = vec![Exit::from(Id::Prisonquart), Exit::from((Id::Nest, 6))];
+        promenade.exits[1].requirements =
+            vec![Requirement::Rune(Rune::Vine), Requirement::BossCells(7)];
+        let biomes = vec![
+            prisonquart,
+            promenade,
+            biome(Id::Toxicsewers, 2, 1),
+            biome(Id::Nest, 15, 1),
+            biome(Id::Nest, 3, 1),
+        ];
+
+        let errors: Vec<String> = validate(&biomes)
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "toxicsewers.column: row 2 column 1 is already taken by promenade",
+                "nest.row: row 15 is outside of 1..=14",
+                "nest.id: duplicate biome id nest",
+                "prisonquart.exits[1].destination: no biome with id castle",
+                "prisonquart.exits[2].destination: duplicate exit to promenade",
+                "promenade.exits[0].destination: exit goes from row 2 to prisonquart at row 1, exits must lead to a higher row",
+                "promenade.exits[1].boss_cell_requirement: requires 6 boss cells but there are only 5",
+                "promenade.exits[1].requirements[1]: requires 7 boss cells but there are only 5",
+                "throne.id: the dataset has no throne room",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_dashes_in_ids() {
+        let mut biomes = core::get_biomes().unwrap();
+        biomes[1].id = Id::new("dilapidated-arboretum");
+        biomes[0].exits[0].destination = Id::new("dilapidated-arboretum");
+
+        assert_eq!(
+            validate(&biomes),
+            vec![ValidationError::new(
+                &Id::new("dilapidated-arboretum"),
+                "id",
+                "biome id dilapidated-arboretum has a dash in it".to_string()
+            )]
+        );
+    }
+
+    fn biome(id: Id, row: usize, column: usize) -> Biome {
+        Biome {
+            name: id.to_string(),
+            id,
+            row,
+            column,
+            power_scrolls: 0,
+            dual_power_scrolls: 0,
+            cursed_chest_chance: 0,
+            scroll_fragments: ScrollFragments::default(),
+            gear_level: 0,
+            exits: vec![],
+            enabled: true,
+            disabled_reasons: vec![],
+        }
+    }
+}
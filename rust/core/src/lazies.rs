@@ -0,0 +1,13 @@
+use crate::graph::Graph;
+use crate::json::models::Biome;
+use crate::path;
+use crate::path::ToggleablePath;
+use crate::precomputed;
+
+lazy_static! {
+    pub static ref BIOMES: Vec<Biome> = precomputed::biomes();
+    pub static ref RAW_PATHS: Vec<ToggleablePath<'static>> =
+        path::precomputed_paths(&BIOMES, precomputed::PATHS);
+    pub static ref GRAPH: Graph<'static> =
+        Graph::precomputed(&BIOMES, precomputed::ORDER, precomputed::EDGES);
+}
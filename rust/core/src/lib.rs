@@ -0,0 +1,26 @@
+//! Route planning for Dead Cells, without anything tied to the browser. The wasm bindings and the
+//! `deadcells-route` command are both built on top of this.
+
+#[macro_use]
+extern crate lazy_static;
+
+#[macro_use]
+pub mod logging;
+
+pub mod blacklist;
+pub mod chests;
+pub mod collectibles;
+pub mod constraints;
+pub mod core;
+pub mod diff;
+pub mod error;
+mod graph;
+pub mod json;
+mod lazies;
+pub mod pareto;
+mod path;
+pub mod planner;
+mod precomputed;
+pub mod reach;
+pub mod scoring;
+pub mod simulation;
@@ -0,0 +1,52 @@
+//! Where log messages go, stderr unless something else is set. The wasm bindings send them to the
+//! browser's `console` instead.
+
+use std::sync::{PoisonError, RwLock};
+
+lazy_static! {
+    static ref LOGGER: RwLock<fn(&str)> = RwLock::new(stderr);
+}
+
+fn stderr(message: &str) {
+    eprintln!("{}", message);
+}
+
+/// Sends every message logged from now on to `logger`
+pub fn set_logger(logger: fn(&str)) {
+    *LOGGER.write().unwrap_or_else(PoisonError::into_inner) = logger;
+}
+
+pub fn log(message: &str) {
+    let logger = *LOGGER.read().unwrap_or_else(PoisonError::into_inner);
+    logger(message);
+}
+
+/// `format!`s its arguments and logs them
+#[macro_export]
+macro_rules! log {
+    ($($arg:tt)*) => {
+        $crate::logging::log(&format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    lazy_static! {
+        static ref LOGGED: Mutex<Vec<String>> = Mutex::new(vec![]);
+    }
+
+    #[test]
+    fn should_log_to_the_logger_that_is_set() {
+        set_logger(|message| LOGGED.lock().unwrap().push(message.to_string()));
+        log!("boss cells: {}", 3);
+        set_logger(stderr);
+
+        assert!(LOGGED
+            .lock()
+            .unwrap()
+            .contains(&"boss cells: 3".to_string()));
+    }
+}
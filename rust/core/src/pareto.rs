@@ -0,0 +1,329 @@
+use crate::blacklist::Blacklist;
+use crate::constraints::Constraints;
+use crate::graph::Graph;
+use crate::json::models::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Something to optimise a route for, summed over the biomes of the route
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    /// power scrolls of the biomes and of the exits between them
+    PowerScrolls,
+    DualPowerScrolls,
+    GearLevel,
+    CursedChestChance,
+    /// fragments at the current amount of boss cells
+    ScrollFragments,
+    /// how many biomes the run goes through
+    Biomes,
+    /// the highest boss cell requirement along the route
+    BossCells,
+}
+
+impl Objective {
+    /// Whether a higher value is better, exposure to cursed chests, long runs and boss cell
+    /// gating are minimised
+    pub fn maximize(self) -> bool {
+        !matches!(
+            self,
+            Objective::CursedChestChance | Objective::Biomes | Objective::BossCells
+        )
+    }
+
+    fn add(self, value: u32, biome: &Biome, exit: Option<&Exit>, boss_cells: u8) -> u32 {
+        match self {
+            Objective::PowerScrolls => {
+                value
+                    + biome.power_scrolls as u32
+                    + exit.and_then(|exit| exit.power_scrolls).unwrap_or(0) as u32
+            }
+            Objective::DualPowerScrolls => value + biome.dual_power_scrolls as u32,
+            Objective::GearLevel => value + biome.gear_level as u32,
+            Objective::CursedChestChance => value + biome.cursed_chest_chance as u32,
+            Objective::ScrollFragments => {
+                value + biome.scroll_fragments.get_fragments(boss_cells) as u32
+            }
+            Objective::Biomes => value + 1,
+            Objective::BossCells => std::cmp::max(
+                value,
+                exit.and_then(|exit| exit.boss_cell_requirement)
+                    .unwrap_or(0) as u32,
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct ParetoRoute {
+    pub route: Vec<Id>,
+    /// one value per requested objective, in the same order
+    pub objectives: Vec<u32>,
+}
+
+struct Label {
+    values: Vec<u32>,
+    previous: Option<(usize, usize)>,
+}
+
+/// Every route from `start` to any of the `ends` that no other route beats on all `objectives`
+/// at once.
+/// Routes with exactly the same values are only returned once, with the lowest biome ids.
+/// Since every objective only ever grows along a route, a route prefix that is dominated at a
+/// biome stays dominated, so only the non-dominated prefixes of every biome are kept.
+pub(crate) fn pareto_routes(
+    graph: &Graph,
+    start: &Id,
+    ends: &[Id],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+    objectives: &[Objective],
+) -> Vec<ParetoRoute> {
+    let start = match graph.index_of(start) {
+        Some(start) => start,
+        None => return vec![],
+    };
+    if blacklist.contains_biome(&graph.biomes[start].id)
+        || !constraints.allows_start(graph.biomes, &graph.biomes[start])
+    {
+        return vec![];
+    }
+    let ends = graph.allowed_ends(ends, constraints);
+    let required = constraints.required(graph.biomes);
+
+    let mut labels: Vec<Vec<Label>> = (0..graph.biomes.len()).map(|_| vec![]).collect();
+    labels[start].push(Label {
+        values: objectives
+            .iter()
+            .map(|objective| objective.add(0, &graph.biomes[start], None, progress.boss_cells))
+            .collect(),
+        previous: None,
+    });
+
+    for &biome in graph.order() {
+        let edges = graph.open_edges(biome, blacklist, progress, constraints, &required);
+        for label in 0..labels[biome].len() {
+            for &(destination, exit) in &edges {
+                let values: Vec<u32> = objectives
+                    .iter()
+                    .zip(labels[biome][label].values.iter())
+                    .map(|(objective, value)| {
+                        objective.add(
+                            *value,
+                            &graph.biomes[destination],
+                            Some(exit),
+                            progress.boss_cells,
+                        )
+                    })
+                    .collect();
+                insert(
+                    &mut labels,
+                    graph,
+                    objectives,
+                    destination,
+                    Label {
+                        values,
+                        previous: Some((biome, label)),
+                    },
+                );
+            }
+        }
+    }
+
+    // every end keeps its own front, so routes to one end can still be beaten by another's
+    let candidates: Vec<ParetoRoute> = ends
+        .iter()
+        .flat_map(|&end| (0..labels[end].len()).map(move |label| (end, label)))
+        .map(|(end, label)| ParetoRoute {
+            route: route_ids(graph, &labels, (end, label)),
+            objectives: labels[end][label].values.clone(),
+        })
+        .collect();
+    let mut routes: Vec<ParetoRoute> = candidates
+        .iter()
+        .filter(|route| {
+            !candidates
+                .iter()
+                .any(|other| dominates(objectives, &other.objectives, &route.objectives))
+        })
+        .cloned()
+        .collect();
+    routes.sort_by(|left, right| {
+        compare(objectives, &right.objectives, &left.objectives)
+            .then_with(|| left.route.cmp(&right.route))
+    });
+    routes.dedup_by(|right, left| left.objectives == right.objectives);
+    routes
+}
+
+/// Adds the label to the biome unless it's dominated, removing the labels it dominates.
+/// Labels are never removed from a biome that has already been expanded since every edge
+/// points forward.
+fn insert(
+    labels: &mut [Vec<Label>],
+    graph: &Graph,
+    objectives: &[Objective],
+    biome: usize,
+    label: Label,
+) {
+    for existing in labels[biome].iter() {
+        if dominates(objectives, &existing.values, &label.values) {
+            return;
+        }
+    }
+    if let Some(index) = labels[biome]
+        .iter()
+        .position(|existing| existing.values == label.values)
+    {
+        let existing = route_ids(graph, labels, (biome, index));
+        let mut candidate = route_ids(graph, labels, label.previous.expect("not the start"));
+        candidate.push(graph.biomes[biome].id.clone());
+        if candidate < existing {
+            labels[biome][index] = label;
+        }
+        return;
+    }
+    labels[biome].retain(|existing| !dominates(objectives, &label.values, &existing.values));
+    labels[biome].push(label);
+}
+
+/// Whether `left` is at least as good as `right` on every objective and better on one
+fn dominates(objectives: &[Objective], left: &[u32], right: &[u32]) -> bool {
+    let mut better = false;
+    for ((objective, left), right) in objectives.iter().zip(left).zip(right) {
+        match better_or_worse(*objective, *left, *right) {
+            Ordering::Less => return false,
+            Ordering::Greater => better = true,
+            Ordering::Equal => {}
+        }
+    }
+    better
+}
+
+fn better_or_worse(objective: Objective, left: u32, right: u32) -> Ordering {
+    if objective.maximize() {
+        left.cmp(&right)
+    } else {
+        right.cmp(&left)
+    }
+}
+
+/// Orders routes by their first objective, then their second and so on
+fn compare(objectives: &[Objective], left: &[u32], right: &[u32]) -> Ordering {
+    objectives
+        .iter()
+        .zip(left.iter().zip(right))
+        .map(|(objective, (left, right))| better_or_worse(*objective, *left, *right))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn route_ids(graph: &Graph, labels: &[Vec<Label>], (biome, label): (usize, usize)) -> Vec<Id> {
+    let mut route = vec![graph.biomes[biome].id.clone()];
+    let mut previous = labels[biome][label].previous;
+    while let Some((biome, label)) = previous {
+        route.push(graph.biomes[biome].id.clone());
+        previous = labels[biome][label].previous;
+    }
+    route.reverse();
+    route
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+    use crate::path;
+
+    #[test]
+    fn should_match_brute_force_pareto_front() {
+        let biomes = core::get_biomes().unwrap();
+        let graph = Graph::new(&biomes).unwrap();
+        let objectives = vec![
+            Objective::PowerScrolls,
+            Objective::ScrollFragments,
+            Objective::CursedChestChance,
+            Objective::Biomes,
+            Objective::BossCells,
+        ];
+        let progress: Progress = 4.into();
+
+        let routes = pareto_routes(
+            &graph,
+            &Id::Prisonquart,
+            &[Id::Throne],
+            &Blacklist::default(),
+            &progress,
+            &Constraints::default(),
+            &objectives,
+        );
+
+        let paths = path::find_paths_between(&biomes, &Id::Prisonquart, &[Id::Throne]).unwrap();
+        let candidates: Vec<Vec<u32>> = paths
+            .iter()
+            .filter(|path| path.minimum_boss_cells <= progress.boss_cells)
+            .map(|path| {
+                let mut values: Vec<u32> = objectives.iter().map(|_| 0).collect();
+                for (i, biome) in path.path.iter().enumerate() {
+                    let exit = i
+                        .checked_sub(1)
+                        .map(|previous| path.path[previous])
+                        .and_then(|previous| {
+                            previous
+                                .exits
+                                .iter()
+                                .find(|exit| exit.destination == biome.id)
+                        });
+                    for (objective, value) in objectives.iter().zip(values.iter_mut()) {
+                        *value = objective.add(*value, biome, exit, progress.boss_cells);
+                    }
+                }
+                values
+            })
+            .collect();
+        let mut expected: Vec<Vec<u32>> = candidates
+            .iter()
+            .filter(|values| {
+                !candidates
+                    .iter()
+                    .any(|other| dominates(&objectives, other, values))
+            })
+            .cloned()
+            .collect();
+        expected.sort();
+        expected.dedup();
+
+        let mut actual: Vec<Vec<u32>> = routes
+            .iter()
+            .map(|route| route.objectives.clone())
+            .collect();
+        actual.sort();
+
+        assert!(routes.len() > 1);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_trade_scrolls_for_fewer_cursed_chests() {
+        let biomes = core::get_biomes().unwrap();
+        let graph = Graph::new(&biomes).unwrap();
+        let objectives = vec![Objective::PowerScrolls, Objective::CursedChestChance];
+
+        let routes = pareto_routes(
+            &graph,
+            &Id::Prisonquart,
+            &[Id::Throne],
+            &Blacklist::default(),
+            &5.into(),
+            &Constraints::default(),
+            &objectives,
+        );
+
+        for (better, worse) in routes.iter().zip(routes.iter().skip(1)) {
+            assert!(better.objectives[0] > worse.objectives[0]);
+            assert!(better.objectives[1] > worse.objectives[1]);
+        }
+    }
+}
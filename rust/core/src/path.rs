@@ -0,0 +1,780 @@
+use crate::blacklist::Blacklist;
+use crate::constraints::{Constraints, ExitId};
+use crate::error::Error;
+use crate::json::models::*;
+use crate::lazies;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Why each of the biomes that can't be reached is disabled
+pub(crate) type DisabledBiomes = HashMap<Id, Vec<DisabledReason>>;
+
+pub(crate) fn get_paths(
+    biomes: Option<&Vec<Biome>>,
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<(Vec<RenderablePath>, DisabledBiomes), Error> {
+    let all_biomes = biomes.unwrap_or(&*lazies::BIOMES);
+    let paths = get_raw_paths(biomes, constraints)?;
+    get_paths_of(all_biomes, &paths, blacklist, progress, constraints)
+}
+
+/// `get_paths` for raw paths that were found before, e.g. the ones a `Planner` keeps
+pub(crate) fn get_paths_of(
+    all_biomes: &[Biome],
+    paths: &[ToggleablePath],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<(Vec<RenderablePath>, DisabledBiomes), Error> {
+    check_constraints(all_biomes, paths, blacklist, progress, constraints)?;
+    get_paths_from(all_biomes, paths, blacklist, progress, constraints)
+}
+
+/// Every path between the constrained start and ends with `enabled` set according to the blacklist, progress and
+/// constraints
+pub(crate) fn get_toggleable_paths<'b>(
+    biomes: Option<&'b Vec<Biome>>,
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<Vec<ToggleablePath<'b>>, Error> {
+    let all_biomes = biomes.unwrap_or(&*lazies::BIOMES);
+    let paths = get_raw_paths(biomes, constraints)?;
+    toggle_paths(all_biomes, &paths, blacklist, progress, constraints)
+}
+
+/// `get_toggleable_paths` for raw paths that were found before
+pub(crate) fn toggle_paths<'b>(
+    all_biomes: &[Biome],
+    paths: &[ToggleablePath<'b>],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<Vec<ToggleablePath<'b>>, Error> {
+    check_constraints(all_biomes, paths, blacklist, progress, constraints)?;
+    Ok(apply_blacklist_and_boss_cells(
+        all_biomes,
+        paths,
+        blacklist,
+        progress,
+        constraints,
+    ))
+}
+
+/// Fails with the constraint that leaves no path enabled, a blacklist or progress that does
+/// that on its own is not an error
+fn check_constraints(
+    all_biomes: &[Biome],
+    paths: &[ToggleablePath],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<(), Error> {
+    constraints.validate(all_biomes)?;
+    let is_met = |constraints: &Constraints| {
+        paths
+            .iter()
+            .any(|path| is_enabled(all_biomes, path, blacklist, progress, constraints))
+    };
+    if constraints.is_empty() || is_met(constraints) {
+        return Ok(());
+    }
+    match constraints.first_unmet(is_met) {
+        Some(constraint) => Err(constraints.no_route(all_biomes, Some(constraint))),
+        None => Ok(()),
+    }
+}
+
+/// Runtime datasets and other starts or ends have their paths calculated on every call, the
+/// compiled-in ones only once
+fn get_raw_paths<'b>(
+    biomes: Option<&'b Vec<Biome>>,
+    constraints: &Constraints,
+) -> Result<Cow<'b, [ToggleablePath<'b>]>, Error> {
+    match biomes {
+        None if constraints.has_default_ends() => Ok(Cow::Borrowed(&lazies::RAW_PATHS)),
+        _ => {
+            let biomes = biomes.unwrap_or(&*lazies::BIOMES);
+            constraints.validate(biomes)?;
+            let start = constraints.start(biomes)?;
+            let ends = constraints.ends(biomes);
+            Ok(Cow::Owned(find_paths_between(biomes, &start, &ends)?))
+        }
+    }
+}
+
+/// The renderable paths and why each of the biomes that can't be reached is disabled
+fn get_paths_from(
+    all_biomes: &[Biome],
+    paths: &[ToggleablePath],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Result<(Vec<RenderablePath>, DisabledBiomes), Error> {
+    let result =
+        apply_blacklist_and_boss_cells(all_biomes, paths, blacklist, progress, constraints);
+    let start = constraints.start(all_biomes).ok();
+    let (mut renderable_paths, reachable_biomes) =
+        biomes_paths_to_paths(all_biomes, start, result)?;
+
+    let exits: HashMap<String, (&Id, &Id)> = all_biomes
+        .iter()
+        .flat_map(|biome| {
+            biome.exits.iter().map(move |exit| {
+                (
+                    format!("{}-{}", biome.id, exit.destination),
+                    (&biome.id, &exit.destination),
+                )
+            })
+        })
+        .collect();
+    for renderable_path in renderable_paths.iter_mut().filter(|path| !path.enabled) {
+        if let Some((from, to)) = exits.get(&renderable_path.id) {
+            let through = paths.iter().filter(|path| {
+                path.path
+                    .windows(2)
+                    .any(|pair| &pair[0].id == *from && &pair[1].id == *to)
+            });
+            renderable_path.disabled_reasons =
+                disabled_reasons(through, &[*from, *to], blacklist, progress);
+        }
+    }
+
+    let disabled_biomes = all_biomes
+        .iter()
+        .filter(|biome| !reachable_biomes.contains(&biome.id))
+        .map(|biome| {
+            let through = paths
+                .iter()
+                .filter(|path| path.path.iter().any(|b| b.id == biome.id));
+            let reasons = disabled_reasons(through, &[&biome.id], blacklist, progress);
+            (biome.id.clone(), reasons)
+        })
+        .collect();
+
+    Ok((renderable_paths, disabled_biomes))
+}
+
+/// Why none of the `paths` through a biome or exit is enabled, `itself` is the biome or both
+/// ends of the exit. Anything the blacklist and boss cells don't explain comes down to runes,
+/// keys or constraints and is reported as unreachable.
+fn disabled_reasons<'p, 'b: 'p>(
+    paths: impl Iterator<Item = &'p ToggleablePath<'b>>,
+    itself: &[&Id],
+    blacklist: &Blacklist,
+    progress: &Progress,
+) -> Vec<DisabledReason> {
+    let is_itself = |from: &Id, to: &Id| itself == [from, to];
+    let mut reasons = vec![];
+    if itself.iter().any(|id| blacklist.contains_biome(id))
+        || itself.len() == 2 && blacklist.contains_exit(itself[0], itself[1])
+    {
+        reasons.push(DisabledReason::Blacklisted);
+    }
+
+    let mut any_path = false;
+    let mut blacklisted_biomes = vec![];
+    let mut blacklisted_exits = vec![];
+    let mut minimum_boss_cells: Option<u8> = None;
+    for path in paths {
+        any_path = true;
+        let biomes: Vec<&Id> = path
+            .path
+            .iter()
+            .map(|biome| &biome.id)
+            .filter(|id| blacklist.contains_biome(id) && !itself.contains(id))
+            .collect();
+        let exits: Vec<ExitId> = path
+            .path
+            .windows(2)
+            .map(|pair| (&pair[0].id, &pair[1].id))
+            .filter(|(from, to)| blacklist.contains_exit(from, to) && !is_itself(from, to))
+            .map(|(from, to)| ExitId {
+                from: from.clone(),
+                to: to.clone(),
+            })
+            .collect();
+        if biomes.is_empty() && exits.is_empty() {
+            minimum_boss_cells = Some(minimum_boss_cells.map_or(path.minimum_boss_cells, |min| {
+                min.min(path.minimum_boss_cells)
+            }));
+        } else {
+            blacklisted_biomes.extend(biomes.into_iter().cloned());
+            blacklisted_exits.extend(exits);
+        }
+    }
+    if !any_path {
+        reasons.push(DisabledReason::Unreachable);
+        return reasons;
+    }
+
+    match minimum_boss_cells {
+        None => {
+            blacklisted_biomes.sort();
+            blacklisted_biomes.dedup();
+            blacklisted_exits.sort();
+            blacklisted_exits.dedup();
+            reasons.push(DisabledReason::BlacklistedRoute {
+                biomes: blacklisted_biomes,
+                exits: blacklisted_exits,
+            });
+        }
+        Some(required) if required > progress.boss_cells => {
+            reasons.push(DisabledReason::BossCells { required });
+        }
+        Some(_) => {}
+    }
+    if reasons.is_empty() {
+        reasons.push(DisabledReason::Unreachable);
+    }
+    reasons
+}
+
+fn calc_columns(biomes: &[Biome], row: usize) -> usize {
+    biomes.iter().filter(|biome| biome.row == row).count()
+}
+
+fn calc_length(start: &Biome, end: &Biome) -> u8 {
+    (end.row - start.row) as u8
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Ord, PartialOrd)]
+pub struct RenderablePath {
+    pub id: String,
+    #[serde(rename = "startColumn")]
+    pub start_column: u8,
+    #[serde(rename = "startColumnOffset")]
+    pub start_column_offset: u8,
+    #[serde(rename = "startColumns")]
+    pub start_columns: u8,
+    #[serde(rename = "endColumn")]
+    pub end_column: u8,
+    #[serde(rename = "endtColumnOffset")]
+    pub end_column_offset: u8,
+    #[serde(rename = "endColumns")]
+    pub end_columns: u8,
+    pub row: u8,
+    pub length: u8,
+    pub enabled: bool,
+    /// why the path is disabled, empty if it isn't
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub disabled_reasons: Vec<DisabledReason>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ToggleablePath<'b> {
+    pub enabled: bool,
+    pub path: Vec<&'b Biome>,
+    pub minimum_boss_cells: u8,
+    /// rune and key requirements of every exit along the path, boss cells are in `minimum_boss_cells`
+    pub requirements: Vec<Requirement>,
+}
+
+impl ToggleablePath<'_> {
+    fn update_requirements(&mut self, exit: &Exit) {
+        for requirement in exit.all_requirements() {
+            match requirement {
+                Requirement::BossCells(boss_cells) => {
+                    self.minimum_boss_cells = std::cmp::max(self.minimum_boss_cells, boss_cells)
+                }
+                requirement => {
+                    if !self.requirements.contains(&requirement) {
+                        self.requirements.push(requirement)
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_satisfied_by(&self, progress: &Progress) -> bool {
+        self.minimum_boss_cells <= progress.boss_cells
+            && self
+                .requirements
+                .iter()
+                .all(|requirement| progress.satisfies(requirement))
+    }
+}
+
+fn biomes_paths_to_paths(
+    all_biomes: &[Biome],
+    start: Option<Id>,
+    biomes: Vec<ToggleablePath>,
+) -> Result<(Vec<RenderablePath>, Vec<Id>), Error> {
+    let mut result = vec![];
+    let mut reachable_biomes: Vec<Id> = start.into_iter().collect();
+
+    for toggleable_path in biomes {
+        let ToggleablePath { enabled, path, .. } = toggleable_path;
+        'inner: for (i, start_biome) in path.iter().enumerate() {
+            let end_biome = match path.get(i + 1) {
+                Some(b) => b,
+                None => break 'inner,
+            };
+
+            let start_id = start_biome.id.clone();
+            let row = start_biome.row as u8;
+            let start_column = start_biome.column as u8;
+            let start_columns = calc_columns(all_biomes, start_biome.row) as u8;
+
+            let end_column = end_biome.column as u8;
+            let end_columns = calc_columns(all_biomes, end_biome.row) as u8;
+            let length = calc_length(start_biome, end_biome);
+
+            let new_path = RenderablePath {
+                id: format!("{}-{}", start_id, end_biome.id),
+                start_column,
+                start_column_offset: 0,
+                start_columns,
+                end_column,
+                end_column_offset: 0,
+                end_columns,
+                row,
+                length,
+                enabled,
+                disabled_reasons: vec![],
+            };
+            // flag this biome as reachable
+            if new_path.enabled && !reachable_biomes.contains(&end_biome.id) {
+                reachable_biomes.push(end_biome.id.clone())
+            }
+            // contains check
+            let existing_path: Option<(usize, &RenderablePath)> = result
+                .iter()
+                .enumerate()
+                .find(|(_, path): &(usize, &RenderablePath)| path.id == new_path.id);
+            match existing_path {
+                Some((index, _)) => {
+                    // if our new path is enabled, make sure the existing one is
+                    if new_path.enabled {
+                        match result.get_mut(index) {
+                            Some(path_to_modify) => {
+                                path_to_modify.enabled = true;
+                            }
+                            None => {
+                                return Err(Error::Internal(format!(
+                                    "path {} disappeared while merging paths",
+                                    new_path.id
+                                )))
+                            }
+                        }
+                    }
+                }
+                None => {
+                    result.push(new_path);
+                }
+            };
+        }
+    }
+
+    Ok((result, reachable_biomes))
+}
+
+fn apply_blacklist_and_boss_cells<'b>(
+    all_biomes: &[Biome],
+    paths: &[ToggleablePath<'b>],
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> Vec<ToggleablePath<'b>> {
+    // todo change enabled instead of creating new paths
+    paths
+        .iter()
+        .map(|path| ToggleablePath {
+            enabled: is_enabled(all_biomes, path, blacklist, progress, constraints),
+            ..path.clone()
+        })
+        .collect()
+}
+
+fn is_enabled(
+    all_biomes: &[Biome],
+    path: &ToggleablePath,
+    blacklist: &Blacklist,
+    progress: &Progress,
+    constraints: &Constraints,
+) -> bool {
+    path.is_satisfied_by(progress)
+        && !blacklist.blocks(&path.path)
+        && constraints.allows_path(all_biomes, &path.path)
+}
+
+/// The paths build.rs found, as biome indexes, see `precomputed::PATHS`
+pub(crate) fn precomputed_paths<'b>(
+    biomes: &'b [Biome],
+    paths: &[&[usize]],
+) -> Vec<ToggleablePath<'b>> {
+    paths
+        .iter()
+        .map(|indexes| {
+            let mut path = ToggleablePath {
+                enabled: true,
+                path: vec![],
+                minimum_boss_cells: 0,
+                requirements: vec![],
+            };
+            for &i in indexes.iter() {
+                let biome = &biomes[i];
+                if let Some(previous) = path.path.last() {
+                    if let Some(exit) = previous
+                        .exits
+                        .iter()
+                        .find(|exit| exit.destination == biome.id)
+                    {
+                        path.update_requirements(exit);
+                    }
+                }
+                path.path.push(biome);
+            }
+            path
+        })
+        .collect()
+}
+
+/// Every path from `start` to any of the `ends`, a path to an end that goes on to another end
+/// is returned once for each of them
+pub(crate) fn find_paths_between<'b>(
+    biomes: &'b Vec<Biome>,
+    start: &Id,
+    ends: &[Id],
+) -> Result<Vec<ToggleablePath<'b>>, Error> {
+    let start = biomes
+        .iter()
+        .find(|biome| &biome.id == start)
+        .ok_or_else(|| Error::InvalidInput(format!("No biome with id {}", start)))?;
+    let start = ToggleablePath {
+        enabled: true,
+        path: vec![start],
+        minimum_boss_cells: 0,
+        requirements: vec![],
+    };
+
+    let paths = find_path_rec(biomes, start, ends);
+
+    Ok(paths)
+}
+
+fn find_path_rec<'b>(
+    all_biomes: &'b Vec<Biome>,
+    current_path: ToggleablePath<'b>,
+    ends: &[Id],
+) -> Vec<ToggleablePath<'b>> {
+    let last_biome_in_path = current_path
+        .path
+        .last()
+        .expect("There should be an element here");
+    let mut paths = vec![];
+    if ends.contains(&last_biome_in_path.id) {
+        paths.push(current_path.clone());
+    }
+
+    let next_biomes: Vec<(&Biome, &Exit)> = all_biomes
+        .iter()
+        .filter_map(|biome| {
+            last_biome_in_path
+                .exits
+                .iter()
+                .find(|exit| exit.destination == biome.id)
+                .map(|exit| (biome, exit))
+        })
+        .collect();
+
+    for (next_biome, exit) in next_biomes {
+        let mut next_path = current_path.clone();
+        next_path.update_requirements(exit);
+        next_path.path.push(next_biome);
+        let mut new_paths = find_path_rec(all_biomes, next_path, ends);
+        paths.append(&mut new_paths)
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::models::ScrollFragments;
+
+    #[test]
+    fn calculate_all_paths() -> Result<(), Error> {
+        let biomes: Vec<Biome> = vec![
+            (
+                Id::Prisonquart,
+                1,
+                1,
+                vec![
+                    (Id::Arboretum, 0),
+                    (Id::Promenade, 0),
+                    (Id::Toxicsewers, 0),
+                    (Id::Ossuary, 5),
+                ],
+            )
+                .into(),
+            (Id::Arboretum, 2, 1, vec![(Id::Prisondepths, 0)]).into(),
+            (
+                Id::Promenade,
+                2,
+                2,
+                vec![(Id::Ossuary, 0), (Id::Corruptedprison, 0)],
+            )
+                .into(),
+            (Id::Toxicsewers, 2, 3, vec![]).into(),
+            (Id::Prisondepths, 3, 1, vec![(Id::Ossuary, 0)]).into(),
+            (Id::Corruptedprison, 3, 2, vec![(Id::Ossuary, 0)]).into(),
+            (Id::Ossuary, 4, 1, vec![]).into(),
+        ];
+
+        let result = find_paths_between(&biomes, &Id::Prisonquart, &[Id::Ossuary])?;
+        // todo check reachable biomes
+        let (result, _) = get_paths_from(
+            &biomes,
+            &result,
+            &vec![Id::Arboretum].into(),
+            &4.into(),
+            &Constraints::default(),
+        )?;
+
+        assert_eq!(
+            result,
+            vec![
+                RenderablePath {
+                    id: "prisonquart-arboretum".to_string(),
+                    start_column: 1,
+                    start_column_offset: 0,
+                    start_columns: 1,
+                    end_column: 1,
+                    end_column_offset: 0,
+                    end_columns: 3,
+                    row: 1,
+                    length: 1,
+                    enabled: false,
+                    disabled_reasons: vec![DisabledReason::Blacklisted],
+                },
+                RenderablePath {
+                    id: "arboretum-prisondepths".to_string(),
+                    start_column: 1,
+                    start_column_offset: 0,
+                    start_columns: 3,
+                    end_column: 1,
+                    end_column_offset: 0,
+                    end_columns: 2,
+                    row: 2,
+                    length: 1,
+                    enabled: false,
+                    disabled_reasons: vec![DisabledReason::Blacklisted],
+                },
+                RenderablePath {
+                    id: "prisondepths-ossuary".to_string(),
+                    start_column: 1,
+                    start_column_offset: 0,
+                    start_columns: 2,
+                    end_column: 1,
+                    end_column_offset: 0,
+                    end_columns: 1,
+                    row: 3,
+                    length: 1,
+                    enabled: false,
+                    disabled_reasons: vec![DisabledReason::BlacklistedRoute {
+                        biomes: vec![Id::Arboretum],
+                        exits: vec![],
+                    }],
+                },
+                RenderablePath {
+                    id: "prisonquart-promenade".to_string(),
+                    start_column: 1,
+                    start_column_offset: 0,
+                    start_columns: 1,
+                    end_column: 2,
+                    end_column_offset: 0,
+                    end_columns: 3,
+                    row: 1,
+                    length: 1,
+                    enabled: true,
+                    disabled_reasons: vec![],
+                },
+                RenderablePath {
+                    id: "promenade-corruptedprison".to_string(),
+                    start_column: 2,
+                    start_column_offset: 0,
+                    start_columns: 3,
+                    end_column: 2,
+                    end_column_offset: 0,
+                    end_columns: 2,
+                    row: 2,
+                    length: 1,
+                    enabled: true,
+                    disabled_reasons: vec![],
+                },
+                RenderablePath {
+                    id: "corruptedprison-ossuary".to_string(),
+                    start_column: 2,
+                    start_column_offset: 0,
+                    start_columns: 2,
+                    end_column: 1,
+                    end_column_offset: 0,
+                    end_columns: 1,
+                    row: 3,
+                    length: 1,
+                    enabled: true,
+                    disabled_reasons: vec![],
+                },
+                RenderablePath {
+                    id: "promenade-ossuary".to_string(),
+                    start_column: 2,
+                    start_column_offset: 0,
+                    start_columns: 3,
+                    end_column: 1,
+                    end_column_offset: 0,
+                    end_columns: 1,
+                    row: 2,
+                    length: 2,
+                    enabled: true,
+                    disabled_reasons: vec![],
+                },
+                RenderablePath {
+                    id: "prisonquart-ossuary".to_string(),
+                    start_column: 1,
+                    start_column_offset: 0,
+                    start_columns: 1,
+                    end_column: 1,
+                    end_column_offset: 0,
+                    end_columns: 1,
+                    row: 1,
+                    length: 3,
+                    enabled: false,
+                    disabled_reasons: vec![DisabledReason::BossCells { required: 5 }],
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_disable_paths_missing_runes_and_keys() -> Result<(), Error> {
+        let mut prisonquart: Biome = (
+            Id::Prisonquart,
+            1,
+            1,
+            vec![(Id::Promenade, 0), (Id::Toxicsewers, 0)],
+        )
+            .into();
+        prisonquart.exits[0].requirements = vec![Requirement::Rune(Rune::Vine)];
+        prisonquart.exits[1].requirements = vec![Requirement::Key("gardener".to_string())];
+        let biomes: Vec<Biome> = vec![
+            prisonquart,
+            (Id::Promenade, 2, 1, vec![(Id::Throne, 0)]).into(),
+            (Id::Toxicsewers, 2, 2, vec![(Id::Throne, 1)]).into(),
+            (Id::Throne, 3, 1, vec![]).into(),
+        ];
+        let paths = find_paths_between(&biomes, &Id::Prisonquart, &[Id::Throne])?;
+
+        let enabled = |progress: Progress| -> Vec<String> {
+            let (paths, _) = get_paths_from(
+                &biomes,
+                &paths,
+                &Blacklist::default(),
+                &progress,
+                &Constraints::default(),
+            )
+            .unwrap();
+            paths
+                .into_iter()
+                .filter(|path| path.enabled)
+                .map(|path| path.id)
+                .collect()
+        };
+
+        assert_eq!(enabled(5.into()), Vec::<String>::new());
+        assert_eq!(
+            enabled(Progress {
+                boss_cells: 0,
+                runes: vec![Rune::Vine],
+                keys: vec!["gardener".to_string()],
+            }),
+            vec!["prisonquart-promenade", "promenade-throne"]
+        );
+        assert_eq!(
+            enabled(Progress {
+                boss_cells: 1,
+                runes: vec![],
+                keys: vec!["gardener".to_string()],
+            }),
+            vec!["prisonquart-toxicsewers", "toxicsewers-throne"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_flag_biomes_that_only_one_enabled_path_goes_through_as_reachable() -> Result<(), Error>
+    {
+        let biomes: Vec<Biome> = vec![
+            (
+                Id::Prisonquart,
+                1,
+                1,
+                vec![(Id::Promenade, 0), (Id::Toxicsewers, 0)],
+            )
+                .into(),
+            (Id::Promenade, 2, 1, vec![(Id::Throne, 0)]).into(),
+            (Id::Toxicsewers, 2, 2, vec![(Id::Throne, 0)]).into(),
+            (Id::Throne, 3, 1, vec![]).into(),
+        ];
+        let paths = find_paths_between(&biomes, &Id::Prisonquart, &[Id::Throne])?;
+
+        let (_, disabled_biomes) = get_paths_from(
+            &biomes,
+            &paths,
+            &vec![Id::Toxicsewers].into(),
+            &5.into(),
+            &Constraints::default(),
+        )?;
+
+        let disabled: Vec<&Id> = disabled_biomes.keys().collect();
+        assert_eq!(disabled, vec![&Id::Toxicsewers]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_exit_requirements() {
+        let exit: Exit = serde_json::from_str(
+            r#"{"destination": "ramparts", "boss_cell_requirement": 1,
+                "requirements": [{"rune": "vine"}, {"key": "gardener"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            exit.all_requirements(),
+            vec![
+                Requirement::BossCells(1),
+                Requirement::Rune(Rune::Vine),
+                Requirement::Key("gardener".to_string()),
+            ]
+        );
+    }
+
+    impl From<(Id, usize, usize, Vec<(Id, u8)>)> for Biome {
+        fn from((id, row, column, exits): (Id, usize, usize, Vec<(Id, u8)>)) -> Self {
+            let name = id.to_string();
+            let exits = exits.into_iter().map(Exit::from).collect();
+            Biome {
+                id,
+                name,
+                row,
+                column,
+                power_scrolls: 0,
+                dual_power_scrolls: 0,
+                cursed_chest_chance: 0,
+                scroll_fragments: ScrollFragments::default(),
+                gear_level: 0,
+                exits,
+                enabled: true,
+                disabled_reasons: vec![],
+            }
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use crate::blacklist::Blacklist;
+use crate::constraints::Constraints;
+use crate::core;
+use crate::core::{BiomeMap, RankedRoute};
+use crate::error::Error;
+use crate::graph::Graph;
+use crate::json::models::*;
+use crate::lazies;
+use crate::path;
+use crate::path::{RenderablePath, ToggleablePath};
+use crate::precomputed;
+use crate::scoring::RouteScorer;
+use serde::Serialize;
+
+/// A dataset with its graph and paths worked out once, and the settings the map is drawn with.
+/// Planners don't share anything, so every dataset can have its own.
+pub struct Planner {
+    biomes: Vec<Biome>,
+    /// the graph and the paths from the start to the ends as biome indexes, see
+    /// `Graph::precomputed` and `path::precomputed_paths`
+    order: Vec<usize>,
+    edges: Vec<Vec<usize>>,
+    paths: Vec<Vec<usize>>,
+    settings: Settings,
+    /// the map as of the last change, the next change is compared against it
+    shown_biomes: Vec<Biome>,
+    shown_paths: Vec<RenderablePath>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Settings {
+    blacklist: Blacklist,
+    progress: Progress,
+    constraints: Constraints,
+}
+
+/// What a change did to the map, biomes and paths that were left as they were aren't in it
+#[derive(Serialize, Debug, Default, PartialEq, Clone)]
+pub struct Changes {
+    /// biomes that got enabled, disabled or are disabled for other reasons now
+    pub biomes: Vec<Biome>,
+    /// paths that are new or changed the same way
+    pub paths: Vec<RenderablePath>,
+    /// ids of paths that are no longer on the map, only new constraints do that
+    pub removed_paths: Vec<String>,
+}
+
+impl Planner {
+    /// A planner for `biomes`, or for the compiled-in biomes.json, with nothing blacklisted, no
+    /// boss cells and no constraints
+    pub fn new(biomes: Option<Vec<Biome>>) -> Result<Self, Error> {
+        let mut planner = match biomes {
+            Some(biomes) => {
+                core::validate_biomes(&biomes)?;
+                let (order, edges) = {
+                    let graph = Graph::new(&biomes)?;
+                    let edges = (0..biomes.len())
+                        .map(|i| graph.edges(i).iter().map(|(to, _)| *to).collect())
+                        .collect();
+                    (graph.order().to_vec(), edges)
+                };
+                let mut planner = Planner::unfinished(biomes, order, edges);
+                planner.paths = planner.find_paths(&Constraints::default())?;
+                planner
+            }
+            None => {
+                let edges = precomputed::EDGES.iter().map(|to| to.to_vec()).collect();
+                let mut planner =
+                    Planner::unfinished(lazies::BIOMES.clone(), precomputed::ORDER.to_vec(), edges);
+                planner.paths = precomputed::PATHS
+                    .iter()
+                    .map(|path| path.to_vec())
+                    .collect();
+                planner
+            }
+        };
+        let (biomes, paths) = planner.draw(&planner.settings, &planner.paths)?;
+        planner.shown_biomes = biomes;
+        planner.shown_paths = paths;
+        Ok(planner)
+    }
+
+    fn unfinished(biomes: Vec<Biome>, order: Vec<usize>, edges: Vec<Vec<usize>>) -> Self {
+        Planner {
+            biomes,
+            order,
+            edges,
+            paths: vec![],
+            settings: Settings::default(),
+            shown_biomes: vec![],
+            shown_paths: vec![],
+        }
+    }
+
+    /// The whole map, `best_route` is always none
+    pub fn map(&self) -> Result<BiomeMap, Error> {
+        let tiers = core::order_biomes_by_tier(self.shown_biomes.clone())?;
+        Ok(BiomeMap::new(tiers, self.shown_paths.clone(), None))
+    }
+
+    /// Blacklists the biome, or takes it off the blacklist if it's on it
+    pub fn toggle_biome(&mut self, id: &Id) -> Result<Changes, Error> {
+        if !self.biomes.iter().any(|biome| &biome.id == id) {
+            return Err(Error::InvalidInput(format!("No biome with id {}", id)));
+        }
+        let mut settings = self.settings.clone();
+        let biomes = &mut settings.blacklist.biomes;
+        match biomes.iter().position(|biome| biome == id) {
+            Some(i) => {
+                biomes.remove(i);
+            }
+            None => biomes.push(id.clone()),
+        }
+        let paths = self.paths.clone();
+        self.update(settings, paths)
+    }
+
+    pub fn set_boss_cells(&mut self, boss_cells: u8) -> Result<Changes, Error> {
+        let mut settings = self.settings.clone();
+        settings.progress.boss_cells = boss_cells;
+        let paths = self.paths.clone();
+        self.update(settings, paths)
+    }
+
+    /// Paths only have to be found again if the start or the end changes
+    pub fn set_constraints(&mut self, constraints: Constraints) -> Result<Changes, Error> {
+        let current = &self.settings.constraints;
+        let paths = if constraints.start == current.start && constraints.end == current.end {
+            constraints.validate(&self.biomes)?;
+            self.paths.clone()
+        } else {
+            self.find_paths(&constraints)?
+        };
+        let settings = Settings {
+            constraints,
+            ..self.settings.clone()
+        };
+        self.update(settings, paths)
+    }
+
+    pub fn best_route(&self, scorer: &dyn RouteScorer) -> Result<RankedRoute, Error> {
+        let Settings {
+            blacklist,
+            progress,
+            constraints,
+        } = &self.settings;
+        let start = constraints.start(&self.biomes)?;
+        let ends = constraints.ends(&self.biomes);
+        core::best_route_in(
+            &self.graph(),
+            &start,
+            &ends,
+            blacklist,
+            progress,
+            constraints,
+            scorer,
+        )
+    }
+
+    pub fn top_routes(
+        &self,
+        scorer: &dyn RouteScorer,
+        k: usize,
+    ) -> Result<Vec<RankedRoute>, Error> {
+        let Settings {
+            blacklist,
+            progress,
+            constraints,
+        } = &self.settings;
+        let paths = path::toggle_paths(
+            &self.biomes,
+            &self.raw_paths(&self.paths),
+            blacklist,
+            progress,
+            constraints,
+        )?;
+        let paths = paths
+            .iter()
+            .filter(|path| path.enabled)
+            .map(|path| &path.path);
+        Ok(core::top_routes(paths, progress.boss_cells, scorer, k))
+    }
+
+    /// Draws the map with the new settings and only takes them if that works
+    fn update(&mut self, settings: Settings, paths: Vec<Vec<usize>>) -> Result<Changes, Error> {
+        let (biomes, renderable_paths) = self.draw(&settings, &paths)?;
+        let changes = Changes {
+            biomes: biomes
+                .iter()
+                .zip(&self.shown_biomes)
+                .filter(|(new, old)| new != old)
+                .map(|(new, _)| new.clone())
+                .collect(),
+            paths: renderable_paths
+                .iter()
+                .filter(|path| !self.shown_paths.contains(path))
+                .cloned()
+                .collect(),
+            removed_paths: self
+                .shown_paths
+                .iter()
+                .filter(|old| !renderable_paths.iter().any(|new| new.id == old.id))
+                .map(|old| old.id.clone())
+                .collect(),
+        };
+        self.settings = settings;
+        self.paths = paths;
+        self.shown_biomes = biomes;
+        self.shown_paths = renderable_paths;
+        Ok(changes)
+    }
+
+    fn draw(
+        &self,
+        settings: &Settings,
+        paths: &[Vec<usize>],
+    ) -> Result<(Vec<Biome>, Vec<RenderablePath>), Error> {
+        let (renderable_paths, disabled) = path::get_paths_of(
+            &self.biomes,
+            &self.raw_paths(paths),
+            &settings.blacklist,
+            &settings.progress,
+            &settings.constraints,
+        )?;
+        Ok((
+            core::disable_biomes(self.biomes.clone(), disabled),
+            renderable_paths,
+        ))
+    }
+
+    fn graph(&self) -> Graph<'_> {
+        let edges: Vec<&[usize]> = self.edges.iter().map(Vec::as_slice).collect();
+        Graph::precomputed(&self.biomes, &self.order, &edges)
+    }
+
+    fn raw_paths(&self, paths: &[Vec<usize>]) -> Vec<ToggleablePath<'_>> {
+        let paths: Vec<&[usize]> = paths.iter().map(Vec::as_slice).collect();
+        path::precomputed_paths(&self.biomes, &paths)
+    }
+
+    fn find_paths(&self, constraints: &Constraints) -> Result<Vec<Vec<usize>>, Error> {
+        constraints.validate(&self.biomes)?;
+        let start = constraints.start(&self.biomes)?;
+        let ends = constraints.ends(&self.biomes);
+        let graph = self.graph();
+        let paths = path::find_paths_between(&self.biomes, &start, &ends)?;
+        Ok(paths
+            .iter()
+            .map(|path| {
+                path.path
+                    .iter()
+                    .filter_map(|biome| graph.index_of(&biome.id))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ScrollScorer;
+
+    fn biome(changes: &Changes, id: Id) -> Option<&Biome> {
+        changes.biomes.iter().find(|biome| biome.id == id)
+    }
+
+    #[test]
+    fn should_only_return_what_changed() {
+        let mut planner = Planner::new(None).unwrap();
+
+        let changes = planner.toggle_biome(&Id::Toxicsewers).unwrap();
+
+        assert!(!biome(&changes, Id::Toxicsewers).unwrap().enabled);
+        assert!(!biome(&changes, Id::Corruptedprison).unwrap().enabled);
+        assert_eq!(biome(&changes, Id::Promenade), None);
+        assert!(changes.paths.iter().all(|path| !path.enabled));
+        assert!(changes.removed_paths.is_empty());
+        let expected = core::get_biome_map(
+            vec![Id::Toxicsewers].into(),
+            &0.into(),
+            &Constraints::default(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(planner.map().unwrap(), expected);
+
+        let changes = planner.set_boss_cells(5).unwrap();
+        assert!(biome(&changes, Id::Lab).unwrap().enabled);
+        assert_eq!(biome(&changes, Id::Toxicsewers), None);
+
+        let changes = planner.toggle_biome(&Id::Toxicsewers).unwrap();
+        assert!(biome(&changes, Id::Toxicsewers).unwrap().enabled);
+        let ids = |changes: Changes| -> Vec<Id> {
+            changes.biomes.into_iter().map(|biome| biome.id).collect()
+        };
+        assert_eq!(
+            ids(planner.toggle_biome(&Id::Toxicsewers).unwrap()),
+            ids(changes)
+        );
+    }
+
+    #[test]
+    fn should_keep_its_settings_when_a_change_fails() {
+        let mut planner = Planner::new(None).unwrap();
+        planner.set_boss_cells(5).unwrap();
+        let map = planner.map().unwrap();
+
+        let unknown = Constraints {
+            required_biomes: vec![Id::new("distillery")],
+            ..Constraints::default()
+        };
+        assert!(planner.set_constraints(unknown).is_err());
+        let impossible = Constraints {
+            required_biomes: vec![Id::Ossuary, Id::Toxicsewers],
+            ..Constraints::default()
+        };
+        assert_eq!(
+            planner.set_constraints(impossible),
+            Err(Error::NoRoute(
+                "No route from prisonquart to throne or observatory through toxicsewers"
+                    .to_string()
+            ))
+        );
+        assert!(planner.toggle_biome(&Id::new("distillery")).is_err());
+
+        assert_eq!(planner.map().unwrap(), map);
+        let scorer = ScrollScorer::default();
+        assert_eq!(
+            planner.best_route(&scorer).unwrap(),
+            core::get_best_route(
+                Blacklist::default(),
+                &5.into(),
+                &Constraints::default(),
+                &scorer,
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn planners_should_keep_to_their_own_dataset() {
+        let dataset = vec![
+            (
+                Id::Prisonquart,
+                1,
+                1,
+                vec![Id::Promenade, Id::Toxicsewers],
+                true,
+            )
+                .into(),
+            (Id::Promenade, 2, 1, vec![Id::Throne], true).into(),
+            (Id::Toxicsewers, 2, 2, vec![Id::Throne], true).into(),
+            (Id::Throne, 3, 1, vec![], true).into(),
+        ];
+        let mut small = Planner::new(Some(dataset)).unwrap();
+        let mut full = Planner::new(None).unwrap();
+
+        let changes = small.toggle_biome(&Id::Promenade).unwrap();
+        full.set_boss_cells(2).unwrap();
+
+        assert_eq!(changes.biomes.len(), 1);
+        assert_eq!(small.map().unwrap().tiers.len(), 3);
+        assert_eq!(
+            small.map().unwrap().reachable,
+            vec![Id::Prisonquart, Id::Toxicsewers, Id::Throne]
+        );
+        let scorer = ScrollScorer::default();
+        let routes = small.top_routes(&scorer, 5).unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(full.top_routes(&scorer, 5).unwrap().len(), 5);
+        assert!(full.map().unwrap().reachable.contains(&Id::Promenade));
+    }
+}
@@ -0,0 +1,39 @@
+//! biomes.json as Rust, along with its graph and paths, generated by build.rs
+
+use crate::json::models::*;
+
+include!(concat!(env!("OUT_DIR"), "/biomes.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Constraints;
+    use crate::core;
+    use crate::graph::Graph;
+    use crate::path;
+
+    #[test]
+    fn should_be_in_sync_with_biomes_json() {
+        let json = core::get_biomes().unwrap();
+
+        assert_eq!(biomes(), json);
+
+        let graph = Graph::new(&json).unwrap();
+        assert_eq!(ORDER, graph.order());
+        for (biome, destinations) in EDGES.iter().enumerate() {
+            let expected: Vec<usize> = graph.edges(biome).iter().map(|(to, _)| *to).collect();
+            assert_eq!(destinations.to_vec(), expected);
+        }
+
+        let constraints = Constraints::default();
+        let start = constraints.start(&json).unwrap();
+        let paths = path::find_paths_between(&json, &start, &constraints.ends(&json)).unwrap();
+        let precomputed = path::precomputed_paths(&json, PATHS);
+        assert_eq!(precomputed.len(), paths.len());
+        for (precomputed, path) in precomputed.iter().zip(&paths) {
+            assert_eq!(precomputed.path, path.path);
+            assert_eq!(precomputed.minimum_boss_cells, path.minimum_boss_cells);
+            assert_eq!(precomputed.requirements, path.requirements);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::blacklist::Blacklist;
+use crate::constraints::ExitId;
+use crate::graph::Graph;
+use crate::json::models::*;
+use serde::Serialize;
+
+/// The fewest boss cells a run needs to get to every biome and through every exit from the start
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct MinimumBossCells {
+    pub biomes: Vec<BiomeBossCells>,
+    pub exits: Vec<ExitBossCells>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct BiomeBossCells {
+    pub id: Id,
+    /// none if the biome can't be reached with any number of boss cells
+    pub boss_cells: Option<u8>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+pub struct ExitBossCells {
+    #[serde(flatten)]
+    pub exit: ExitId,
+    /// none if the exit can't be taken with any number of boss cells
+    pub boss_cells: Option<u8>,
+}
+
+/// Works through the biomes in topological order, a biome needs as many boss cells as the
+/// cheapest exit into it. Blacklisted biomes and exits with rune or key requirements `progress`
+/// doesn't meet are never taken, the boss cells in `progress` don't matter.
+pub fn minimum_boss_cells(
+    graph: &Graph,
+    start: &Id,
+    blacklist: &Blacklist,
+    progress: &Progress,
+) -> MinimumBossCells {
+    let mut biomes: Vec<Option<u8>> = vec![None; graph.biomes.len()];
+    let mut exits = vec![];
+    if let Some(start) = graph.index_of(start) {
+        if !blacklist.contains_biome(&graph.biomes[start].id) {
+            biomes[start] = Some(0);
+        }
+    }
+
+    for &from in graph.order() {
+        for &(to, exit) in graph.edges(from) {
+            let from_id = &graph.biomes[from].id;
+            let boss_cells = biomes[from].and_then(|boss_cells| {
+                Some(boss_cells.max(needed(from_id, exit, blacklist, progress)?))
+            });
+            if let Some(boss_cells) = boss_cells {
+                biomes[to] = Some(biomes[to].map_or(boss_cells, |known| known.min(boss_cells)));
+            }
+            exits.push(ExitBossCells {
+                exit: ExitId {
+                    from: graph.biomes[from].id.clone(),
+                    to: exit.destination.clone(),
+                },
+                boss_cells,
+            });
+        }
+    }
+
+    MinimumBossCells {
+        biomes: graph
+            .biomes
+            .iter()
+            .zip(biomes)
+            .map(|(biome, boss_cells)| BiomeBossCells {
+                id: biome.id.clone(),
+                boss_cells,
+            })
+            .collect(),
+        exits,
+    }
+}
+
+/// The boss cells the exit needs, none if it can't be taken at all
+fn needed(from: &Id, exit: &Exit, blacklist: &Blacklist, progress: &Progress) -> Option<u8> {
+    if !blacklist.allows_exit(from, &exit.destination) {
+        return None;
+    }
+    let mut needed = 0;
+    for requirement in exit.all_requirements() {
+        match requirement {
+            Requirement::BossCells(boss_cells) => needed = needed.max(boss_cells),
+            requirement if progress.satisfies(&requirement) => {}
+            _ => return None,
+        }
+    }
+    Some(needed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core;
+
+    fn of_biome(minimum: &MinimumBossCells, id: Id) -> Option<u8> {
+        minimum
+            .biomes
+            .iter()
+            .find(|biome| biome.id == id)
+            .unwrap()
+            .boss_cells
+    }
+
+    fn of_exit(minimum: &MinimumBossCells, from: Id, to: Id) -> Option<u8> {
+        minimum
+            .exits
+            .iter()
+            .find(|exit| exit.exit.from == from && exit.exit.to == to)
+            .unwrap()
+            .boss_cells
+    }
+
+    #[test]
+    fn should_find_the_cheapest_way_to_every_biome() {
+        let biomes = core::get_biomes().unwrap();
+        let graph = Graph::new(&biomes).unwrap();
+
+        let minimum = minimum_boss_cells(
+            &graph,
+            &Id::Prisonquart,
+            &Blacklist::default(),
+            &Progress::default(),
+        );
+
+        assert_eq!(of_biome(&minimum, Id::Prisonquart), Some(0));
+        assert_eq!(of_biome(&minimum, Id::Ancientsewers), Some(0));
+        assert_eq!(of_biome(&minimum, Id::Cavern), Some(0));
+        assert_eq!(of_biome(&minimum, Id::Lab), Some(5));
+        assert_eq!(of_biome(&minimum, Id::Observatory), Some(5));
+        assert_eq!(of_exit(&minimum, Id::Slumbering, Id::Cavern), Some(2));
+        assert_eq!(of_exit(&minimum, Id::Ramparts, Id::Crypt), Some(3));
+        assert_eq!(
+            minimum.exits.len(),
+            biomes.iter().map(|b| b.exits.len()).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn should_route_around_the_blacklist() {
+        let biomes = core::get_biomes().unwrap();
+        let graph = Graph::new(&biomes).unwrap();
+        let blacklist = vec![Id::Toxicsewers, Id::Graveyard].into();
+
+        let minimum = minimum_boss_cells(&graph, &Id::Prisonquart, &blacklist, &0.into());
+
+        assert_eq!(of_biome(&minimum, Id::Toxicsewers), None);
+        assert_eq!(of_biome(&minimum, Id::Corruptedprison), None);
+        assert_eq!(of_biome(&minimum, Id::Ancientsewers), Some(1));
+        assert_eq!(of_biome(&minimum, Id::Cavern), Some(2));
+        assert_eq!(of_exit(&minimum, Id::Crypt, Id::Graveyard), None);
+        assert_eq!(of_exit(&minimum, Id::Corruptedprison, Id::Ramparts), None);
+
+        let minimum = minimum_boss_cells(
+            &graph,
+            &Id::Prisonquart,
+            &vec![Id::Prisonquart].into(),
+            &0.into(),
+        );
+        assert!(minimum
+            .biomes
+            .iter()
+            .all(|biome| biome.boss_cells.is_none()));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::json::models::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Everything a route adds up to, this is what scorers get to look at. Adding saturates
+/// instead of overflowing.
+#[derive(Serialize, Deserialize, Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct RouteTotals {
+    pub power_scrolls: u32,
+    pub dual_power_scrolls: u32,
+    pub scroll_fragments: u32,
+    pub cursed_chest_chance: u32,
+    pub transition_scrolls: u32,
+    pub gear_level: u32,
+}
+
+impl RouteTotals {
+    pub fn of(path: &[&Biome], boss_cells: u8) -> Self {
+        RouteTotals::running(path, boss_cells)
+            .last()
+            .map(|(_, totals)| totals)
+            .unwrap_or_default()
+    }
+
+    /// Every biome of `path` with the totals up to and including it
+    pub(crate) fn running<'a, 'b>(
+        path: &'a [&'b Biome],
+        boss_cells: u8,
+    ) -> impl Iterator<Item = (&'b Biome, RouteTotals)> + 'a {
+        path.iter()
+            .enumerate()
+            .scan(RouteTotals::default(), move |totals, (i, biome)| {
+                if i > 0 {
+                    if let Some(exit) = path[i - 1]
+                        .exits
+                        .iter()
+                        .find(|exit| exit.destination == biome.id)
+                    {
+                        totals.add_exit(exit);
+                    }
+                }
+                totals.add_biome(biome, boss_cells);
+                Some((*biome, *totals))
+            })
+    }
+
+    pub(crate) fn add_biome(&mut self, biome: &Biome, boss_cells: u8) {
+        let add = |total: u32, value: u8| total.saturating_add(value as u32);
+        self.power_scrolls = add(self.power_scrolls, biome.power_scrolls);
+        self.dual_power_scrolls = add(self.dual_power_scrolls, biome.dual_power_scrolls);
+        self.scroll_fragments = add(
+            self.scroll_fragments,
+            biome.scroll_fragments.get_fragments(boss_cells),
+        );
+        self.cursed_chest_chance = add(self.cursed_chest_chance, biome.cursed_chest_chance);
+        self.gear_level = add(self.gear_level, biome.gear_level);
+    }
+
+    pub(crate) fn add_exit(&mut self, exit: &Exit) {
+        self.transition_scrolls = self
+            .transition_scrolls
+            .saturating_add(exit.power_scrolls.unwrap_or(0) as u32);
+    }
+
+    /// Every 4 fragments make a scroll
+    pub fn scrolls_from_fragments(&self) -> u32 {
+        self.scroll_fragments / 4
+    }
+
+    /// Every cursed chest gives a scroll, this is how many chests to expect, see `CursedChests`
+    pub fn scrolls_from_cursed_chests(&self) -> f64 {
+        self.cursed_chest_chance as f64 / 100.
+    }
+}
+
+/// Scores this close are a tie. Cursed chests add fractions, so the same score can come out as
+/// 9.12 or 9.120000000000001 depending on how it was added up.
+pub const SCORE_EPSILON: f64 = 1e-9;
+
+/// `Greater` if `left` is the better score, `Equal` if they only differ by float noise
+pub fn compare_scores(left: f64, right: f64) -> Ordering {
+    if (left - right).abs() <= SCORE_EPSILON {
+        Ordering::Equal
+    } else {
+        left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Decides how good a route is, higher is better.
+///
+/// `graph::best_route` only compares route prefixes that end in the same biome with the same
+/// fragments (mod 4), so a scorer has to rank those the same way however the route continues.
+/// Any weighted sum of the totals and the scrolls from fragments and cursed chests does.
+pub trait RouteScorer {
+    fn score(&self, totals: &RouteTotals) -> f64;
+}
+
+/// Counts scrolls, the way routes have always been scored
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScrollScorer {
+    pub include_dual_scrolls: bool,
+}
+
+impl RouteScorer for ScrollScorer {
+    fn score(&self, totals: &RouteTotals) -> f64 {
+        let dual_scrolls = if self.include_dual_scrolls {
+            totals.dual_power_scrolls
+        } else {
+            0
+        };
+        totals.power_scrolls as f64
+            + dual_scrolls as f64
+            + totals.scrolls_from_fragments() as f64
+            + totals.transition_scrolls as f64
+            + totals.scrolls_from_cursed_chests()
+    }
+}
+
+/// A weight per collectible, e.g. `{"dual_power_scrolls": 0.5, "cursed_chest_chance": -0.01}`.
+/// Weights left out default to counting scrolls without dual scrolls.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeightedScorer {
+    pub power_scrolls: f64,
+    pub dual_power_scrolls: f64,
+    pub scrolls_from_fragments: f64,
+    pub scrolls_from_cursed_chests: f64,
+    pub transition_scrolls: f64,
+    /// per percent of cursed chest chance, negative to stay away from cursed chests
+    pub cursed_chest_chance: f64,
+    pub gear_level: f64,
+}
+
+impl Default for WeightedScorer {
+    fn default() -> Self {
+        WeightedScorer {
+            power_scrolls: 1.,
+            dual_power_scrolls: 0.,
+            scrolls_from_fragments: 1.,
+            scrolls_from_cursed_chests: 1.,
+            transition_scrolls: 1.,
+            cursed_chest_chance: 0.,
+            gear_level: 0.,
+        }
+    }
+}
+
+impl RouteScorer for WeightedScorer {
+    fn score(&self, totals: &RouteTotals) -> f64 {
+        self.power_scrolls * totals.power_scrolls as f64
+            + self.dual_power_scrolls * totals.dual_power_scrolls as f64
+            + self.scrolls_from_fragments * totals.scrolls_from_fragments() as f64
+            + self.scrolls_from_cursed_chests * totals.scrolls_from_cursed_chests()
+            + self.transition_scrolls * totals.transition_scrolls as f64
+            + self.cursed_chest_chance * totals.cursed_chest_chance as f64
+            + self.gear_level * totals.gear_level as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_weights_should_count_scrolls() {
+        let totals = RouteTotals {
+            power_scrolls: 12,
+            dual_power_scrolls: 7,
+            scroll_fragments: 23,
+            cursed_chest_chance: 250,
+            transition_scrolls: 2,
+            gear_level: 40,
+        };
+
+        assert_eq!(ScrollScorer::default().score(&totals), 21.5);
+        assert_eq!(WeightedScorer::default().score(&totals), 21.5);
+        assert_eq!(
+            ScrollScorer {
+                include_dual_scrolls: true
+            }
+            .score(&totals),
+            28.5
+        );
+    }
+
+    #[test]
+    fn should_parse_partial_weights() {
+        let scorer: WeightedScorer =
+            serde_json::from_str(r#"{"dual_power_scrolls": 0.5, "cursed_chest_chance": -0.01}"#)
+                .unwrap();
+        let totals = RouteTotals {
+            power_scrolls: 10,
+            dual_power_scrolls: 4,
+            cursed_chest_chance: 100,
+            ..RouteTotals::default()
+        };
+
+        assert_eq!(scorer.score(&totals), 10. + 2. + 1. - 1.);
+    }
+
+    #[test]
+    fn scores_that_only_differ_by_float_noise_should_tie() {
+        let score = |power_scrolls, cursed_chest_chance| {
+            ScrollScorer::default().score(&RouteTotals {
+                power_scrolls,
+                cursed_chest_chance,
+                ..RouteTotals::default()
+            })
+        };
+
+        assert_ne!(score(8, 112), score(9, 12));
+        assert_eq!(compare_scores(score(8, 112), score(9, 12)), Ordering::Equal);
+        assert_eq!(
+            compare_scores(score(9, 13), score(9, 12)),
+            Ordering::Greater
+        );
+        assert_eq!(compare_scores(score(8, 12), score(9, 12)), Ordering::Less);
+    }
+}
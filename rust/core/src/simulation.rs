@@ -0,0 +1,211 @@
+use crate::error::Error;
+use crate::json::models::*;
+use crate::scoring::RouteTotals;
+use serde::Serialize;
+
+/// The most runs a simulation plays, every run keeps a sample around until the percentiles are
+/// worked out
+pub const MAX_RUNS: u32 = 1_000_000;
+
+/// How a route plays out over many runs. Everything but the cursed chests is fixed by the
+/// route and the boss cells, so that's where the spread comes from.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct Simulation {
+    pub runs: u32,
+    pub seed: u64,
+    pub scrolls: Distribution,
+    /// scrolls made out of 4 fragments each, the same every run
+    pub fragments_converted: u32,
+    pub cursed_chests: Distribution,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct Distribution {
+    /// `histogram[n]` is how many runs ended up with `n`
+    pub histogram: Vec<u32>,
+    pub mean: f64,
+    pub min: u32,
+    pub p5: u32,
+    pub p25: u32,
+    pub median: u32,
+    pub p75: u32,
+    pub p95: u32,
+    pub max: u32,
+}
+
+impl Distribution {
+    fn of(mut samples: Vec<u32>) -> Self {
+        samples.sort_unstable();
+        let max = *samples.last().expect("at least one run");
+        let mut histogram = vec![0; max as usize + 1];
+        for sample in &samples {
+            histogram[*sample as usize] += 1;
+        }
+        let percentile = |percent: usize| samples[(samples.len() - 1) * percent / 100];
+        Distribution {
+            mean: samples.iter().map(|sample| *sample as f64).sum::<f64>() / samples.len() as f64,
+            min: samples[0],
+            p5: percentile(5),
+            p25: percentile(25),
+            median: percentile(50),
+            p75: percentile(75),
+            p95: percentile(95),
+            max,
+            histogram,
+        }
+    }
+}
+
+/// Plays the route `runs` times, from 1 to `MAX_RUNS`. The same seed always gives the same
+/// simulation, on every platform.
+pub fn simulate(
+    path: &[&Biome],
+    boss_cells: u8,
+    include_dual_scrolls: bool,
+    runs: u32,
+    seed: u64,
+) -> Result<Simulation, Error> {
+    if runs == 0 {
+        return Err(Error::InvalidInput("Can't simulate 0 runs".to_string()));
+    }
+    if runs > MAX_RUNS {
+        return Err(Error::InvalidInput(format!(
+            "Can't simulate more than {} runs, not {}",
+            MAX_RUNS, runs
+        )));
+    }
+    let totals = RouteTotals::of(path, boss_cells);
+    let fragments_converted = totals.scrolls_from_fragments();
+    let mut fixed_scrolls = totals
+        .power_scrolls
+        .saturating_add(totals.transition_scrolls)
+        .saturating_add(fragments_converted);
+    if include_dual_scrolls {
+        fixed_scrolls = fixed_scrolls.saturating_add(totals.dual_power_scrolls);
+    }
+
+    let mut random = SplitMix64(seed);
+    let mut scrolls = vec![];
+    let mut cursed_chests = vec![];
+    for _ in 0..runs {
+        let chests: u32 = path
+            .iter()
+            .map(|biome| {
+                let certain = (biome.cursed_chest_chance / 100) as u32;
+                let maybe = (biome.cursed_chest_chance % 100) as f64 / 100.;
+                certain + if random.next_f64() < maybe { 1 } else { 0 }
+            })
+            .sum();
+        cursed_chests.push(chests);
+        scrolls.push(fixed_scrolls.saturating_add(chests));
+    }
+
+    Ok(Simulation {
+        runs,
+        seed,
+        scrolls: Distribution::of(scrolls),
+        fragments_converted,
+        cursed_chests: Distribution::of(cursed_chests),
+    })
+}
+
+/// A small, fast generator that gives the same numbers everywhere, which is all a simulation
+/// needs. See http://prng.di.unimi.it/splitmix64.c
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chests::CursedChests;
+    use crate::core;
+
+    fn route<'b>(biomes: &'b [Biome], ids: &[&str]) -> Vec<&'b Biome> {
+        ids.iter()
+            .map(|id| biomes.iter().find(|biome| biome.id == Id::new(id)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_be_deterministic_per_seed() {
+        let biomes = core::get_biomes().unwrap();
+        let path = route(&biomes, &["prisonquart", "promenade", "ossuary", "bridge"]);
+
+        let simulation = simulate(&path, 3, false, 1000, 42).unwrap();
+
+        assert_eq!(simulation, simulate(&path, 3, false, 1000, 42).unwrap());
+        assert_ne!(
+            simulation.cursed_chests.histogram,
+            simulate(&path, 3, false, 1000, 43)
+                .unwrap()
+                .cursed_chests
+                .histogram
+        );
+        assert_eq!(simulation.scrolls.histogram.iter().sum::<u32>(), 1000);
+        assert_eq!(
+            simulate(&path, 3, false, 0, 42),
+            Err(Error::InvalidInput("Can't simulate 0 runs".to_string()))
+        );
+        assert!(simulate(&path, 3, false, MAX_RUNS, 42).is_ok());
+        assert_eq!(
+            simulate(&path, 3, false, 4_000_000_000, 42),
+            Err(Error::InvalidInput(
+                "Can't simulate more than 1000000 runs, not 4000000000".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn should_match_the_cursed_chest_odds() {
+        let biomes = core::get_biomes().unwrap();
+        let path = route(
+            &biomes,
+            &[
+                "prisonquart",
+                "arboretum",
+                "prisondepths",
+                "ancientsewers",
+                "crypt",
+                "slumbering",
+                "cavern",
+                "haven",
+                "throne",
+            ],
+        );
+        let runs = 20_000;
+
+        let simulation = simulate(&path, 5, false, runs, 7).unwrap();
+        let odds = CursedChests::for_path(&path);
+
+        assert!((simulation.cursed_chests.mean - odds.expected).abs() < 0.05);
+        for chests in 0..odds.at_least.len() {
+            let simulated: u32 = simulation.cursed_chests.histogram.iter().skip(chests).sum();
+            let simulated = simulated as f64 / runs as f64;
+            assert!(
+                (simulated - odds.probability_of_at_least(chests)).abs() < 0.02,
+                "at least {} chests",
+                chests
+            );
+        }
+        // fragments don't depend on luck
+        assert_eq!(simulation.fragments_converted, 6);
+        let scrolls = &simulation.scrolls;
+        assert!(scrolls.min <= scrolls.p5 && scrolls.p5 <= scrolls.p25);
+        assert!(scrolls.p25 <= scrolls.median && scrolls.median <= scrolls.p75);
+        assert!(scrolls.p75 <= scrolls.p95 && scrolls.p95 <= scrolls.max);
+    }
+}
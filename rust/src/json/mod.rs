@@ -1,2 +0,0 @@
-pub(crate) mod json;
-pub(crate) mod models;
@@ -0,0 +1,453 @@
+//! The JS side of the planner, turns `JsValue`s into the core crate's types and back
+
+use deadcells_route_core::blacklist::Blacklist;
+use deadcells_route_core::constraints::Constraints;
+use deadcells_route_core::core;
+use deadcells_route_core::error::Error;
+use deadcells_route_core::json::models::*;
+use deadcells_route_core::json::validation;
+use deadcells_route_core::log;
+use deadcells_route_core::logging;
+use deadcells_route_core::pareto::Objective;
+use deadcells_route_core::planner;
+use deadcells_route_core::scoring::{RouteScorer, ScrollScorer, WeightedScorer};
+use js_sys::Array;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+
+// When the `wee_alloc` feature is enabled, this uses `wee_alloc` as the global
+// allocator.
+//
+// If you don't want to use `wee_alloc`, you can safely delete this.
+#[cfg(feature = "wee_alloc")]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+/// Runs when the module is loaded, sends the core crate's log messages to `console`
+#[wasm_bindgen(start)]
+pub fn start() {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+
+    logging::set_logger(|message| console::log_1(&JsValue::from(message)));
+}
+
+/// Returns `{tiers, paths, reachable, best_route}`, the biomes row by row, the exits between them,
+/// the ids of the enabled biomes and the best route if `scoring` is given, see `parse_scorer`.
+/// Disabled biomes and paths say why in `disabled_reasons`.
+#[wasm_bindgen(js_name = getBiomes)]
+pub fn get_biomes(
+    blacklist: Array,
+    boss_cells: JsValue,
+    dataset: JsValue,
+    progress: JsValue,
+    constraints: JsValue,
+    scoring: JsValue,
+) -> Result<JsValue, JsValue> {
+    let blacklist = parse_blacklist(&blacklist).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    log!("lib:: boss cells: {}", boss_cells);
+
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+    let progress = parse_progress(&progress, boss_cells).map_err(js_error)?;
+    let constraints = parse_constraints(&constraints).map_err(js_error)?;
+    // unlike the other entry points no scoring means no best route
+    let scorer = if scoring.is_undefined() || scoring.is_null() {
+        None
+    } else {
+        Some(parse_scorer(&scoring).map_err(js_error)?)
+    };
+
+    let map = core::get_biome_map(
+        blacklist,
+        &progress,
+        &constraints,
+        scorer.as_deref(),
+        dataset,
+    )
+    .map_err(js_error)?;
+
+    to_js(&map, "biome map")
+}
+
+/// Returns the `k` best scored routes as `{route, score, behind_best, collectibles}`, see
+/// `parse_scorer` for `scoring`
+#[wasm_bindgen(js_name = getTopRoutes)]
+pub fn get_top_routes(
+    blacklist: Array,
+    boss_cells: JsValue,
+    scoring: JsValue,
+    k: usize,
+    dataset: JsValue,
+    progress: JsValue,
+    constraints: JsValue,
+) -> Result<JsValue, JsValue> {
+    let blacklist = parse_blacklist(&blacklist).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+    let progress = parse_progress(&progress, boss_cells).map_err(js_error)?;
+    let constraints = parse_constraints(&constraints).map_err(js_error)?;
+    let scorer = parse_scorer(&scoring).map_err(js_error)?;
+
+    let routes = core::get_top_routes(
+        blacklist,
+        &progress,
+        &constraints,
+        scorer.as_ref(),
+        k,
+        dataset,
+    )
+    .map_err(js_error)?;
+
+    to_js(&routes, "routes")
+}
+
+/// Returns the best scored route as `{route, score, behind_best, collectibles}`
+#[wasm_bindgen(js_name = getBestRoute)]
+pub fn get_best_route(
+    blacklist: Array,
+    boss_cells: JsValue,
+    scoring: JsValue,
+    dataset: JsValue,
+    progress: JsValue,
+    constraints: JsValue,
+) -> Result<JsValue, JsValue> {
+    let blacklist = parse_blacklist(&blacklist).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+    let progress = parse_progress(&progress, boss_cells).map_err(js_error)?;
+    let constraints = parse_constraints(&constraints).map_err(js_error)?;
+    let scorer = parse_scorer(&scoring).map_err(js_error)?;
+
+    let route = core::get_best_route(blacklist, &progress, &constraints, scorer.as_ref(), dataset)
+        .map_err(js_error)?;
+
+    to_js(&route, "route")
+}
+
+/// Returns the routes no other route beats on all objectives as `{route, objectives}`, where
+/// objectives are e.g. `["power_scrolls", "cursed_chest_chance"]`
+#[wasm_bindgen(js_name = getParetoRoutes)]
+pub fn get_pareto_routes(
+    blacklist: Array,
+    boss_cells: JsValue,
+    objectives: JsValue,
+    dataset: JsValue,
+    progress: JsValue,
+    constraints: JsValue,
+) -> Result<JsValue, JsValue> {
+    let blacklist = parse_blacklist(&blacklist).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    let objectives: Vec<Objective> = JsValue::into_serde(&objectives)
+        .map_err(|err| Error::InvalidInput(format!("Failed to parse objectives: {}", err)))
+        .map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+    let progress = parse_progress(&progress, boss_cells).map_err(js_error)?;
+    let constraints = parse_constraints(&constraints).map_err(js_error)?;
+
+    let routes = core::get_pareto_routes(blacklist, &progress, &constraints, &objectives, dataset)
+        .map_err(js_error)?;
+
+    to_js(&routes, "routes")
+}
+
+/// Plays the route, an array of biome ids, `runs` times, up to a million, and returns histograms
+/// and percentiles of its scrolls and cursed chests, along with the scrolls fragments make
+#[wasm_bindgen(js_name = simulateRoute)]
+pub fn simulate_route(
+    route: Array,
+    boss_cells: JsValue,
+    include_dual_scrolls: bool,
+    runs: u32,
+    seed: u32,
+    dataset: JsValue,
+) -> Result<JsValue, JsValue> {
+    let route = parse_route(&route).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+
+    let simulation = core::simulate_route(
+        &route,
+        boss_cells,
+        include_dual_scrolls,
+        runs,
+        seed as u64,
+        dataset,
+    )
+    .map_err(js_error)?;
+
+    to_js(&simulation, "simulation")
+}
+
+/// Returns what every biome of the route, an array of biome ids, adds as
+/// `{biome, power_scrolls, ..., running_totals, converts_fragments}`
+#[wasm_bindgen(js_name = explainRoute)]
+pub fn explain_route(
+    route: Array,
+    boss_cells: JsValue,
+    dataset: JsValue,
+) -> Result<JsValue, JsValue> {
+    let route = parse_route(&route).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+
+    let explanation = core::explain_route(&route, boss_cells, dataset).map_err(js_error)?;
+
+    to_js(&explanation, "explanation")
+}
+
+/// Compares two routes, arrays of biome ids, returns `{segments, delta}` with a
+/// `{diverges_after, merges_at, first, second, delta}` per stretch where they part ways
+#[wasm_bindgen(js_name = diffRoutes)]
+pub fn diff_routes(
+    first: Array,
+    second: Array,
+    boss_cells: JsValue,
+    dataset: JsValue,
+) -> Result<JsValue, JsValue> {
+    let first = parse_route(&first).map_err(js_error)?;
+    let second = parse_route(&second).map_err(js_error)?;
+    let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+
+    let diff = core::diff_routes(&first, &second, boss_cells, dataset).map_err(js_error)?;
+
+    to_js(&diff, "route diff")
+}
+
+/// Returns the fewest boss cells needed to get to every biome and through every exit from the
+/// start as `{biomes: [{id, boss_cells}], exits: [{from, to, boss_cells}]}`, `boss_cells` is
+/// `null` where no number of boss cells is enough
+#[wasm_bindgen(js_name = getMinimumBossCells)]
+pub fn get_minimum_boss_cells(
+    blacklist: Array,
+    dataset: JsValue,
+    progress: JsValue,
+    constraints: JsValue,
+) -> Result<JsValue, JsValue> {
+    let blacklist = parse_blacklist(&blacklist).map_err(js_error)?;
+    let dataset = parse_dataset(&dataset).map_err(js_error)?;
+    let progress = parse_progress(&progress, 0).map_err(js_error)?;
+    let constraints = parse_constraints(&constraints).map_err(js_error)?;
+
+    let minimum = core::get_minimum_boss_cells(blacklist, &progress, &constraints, dataset)
+        .map_err(js_error)?;
+
+    to_js(&minimum, "minimum boss cells")
+}
+
+/// Returns every structural problem in the dataset as `{biome, field, message}` objects
+#[wasm_bindgen(js_name = validateBiomes)]
+pub fn validate_biomes(dataset: JsValue) -> Result<JsValue, JsValue> {
+    let biomes: Vec<Biome> = match parse_dataset(&dataset).map_err(js_error)? {
+        Some(biomes) => biomes,
+        None => core::get_biomes().map_err(js_error)?,
+    };
+
+    let errors = validation::validate(&biomes);
+    to_js(&errors, "validation errors")
+}
+
+/// Keeps a dataset and the blacklist, boss cells and constraints between calls, the methods that
+/// change them return `{biomes, paths, removed_paths}` with only what changed on the map
+#[wasm_bindgen(js_name = Planner)]
+pub struct JsPlanner {
+    planner: planner::Planner,
+}
+
+#[wasm_bindgen(js_class = Planner)]
+impl JsPlanner {
+    /// See `getBiomes` for `dataset`
+    #[wasm_bindgen(constructor)]
+    pub fn new(dataset: JsValue) -> Result<JsPlanner, JsValue> {
+        let dataset = parse_dataset(&dataset).map_err(js_error)?;
+        let planner = planner::Planner::new(dataset).map_err(js_error)?;
+        Ok(JsPlanner { planner })
+    }
+
+    /// The whole map as `{tiers, paths, reachable}`, like `getBiomes`
+    #[wasm_bindgen(js_name = getBiomes)]
+    pub fn get_biomes(&self) -> Result<JsValue, JsValue> {
+        let map = self.planner.map().map_err(js_error)?;
+        to_js(&map, "biome map")
+    }
+
+    #[wasm_bindgen(js_name = toggleBiome)]
+    pub fn toggle_biome(&mut self, id: String) -> Result<JsValue, JsValue> {
+        let changes = self.planner.toggle_biome(&Id::new(&id)).map_err(js_error)?;
+        to_js(&changes, "changes")
+    }
+
+    #[wasm_bindgen(js_name = setBossCells)]
+    pub fn set_boss_cells(&mut self, boss_cells: JsValue) -> Result<JsValue, JsValue> {
+        let boss_cells = parse_boss_cells(&boss_cells).map_err(js_error)?;
+        let changes = self.planner.set_boss_cells(boss_cells).map_err(js_error)?;
+        to_js(&changes, "changes")
+    }
+
+    /// See `parse_constraints`
+    #[wasm_bindgen(js_name = setConstraints)]
+    pub fn set_constraints(&mut self, constraints: JsValue) -> Result<JsValue, JsValue> {
+        let constraints = parse_constraints(&constraints).map_err(js_error)?;
+        let changes = self
+            .planner
+            .set_constraints(constraints)
+            .map_err(js_error)?;
+        to_js(&changes, "changes")
+    }
+
+    /// Like `getBestRoute` with the planner's settings
+    #[wasm_bindgen(js_name = getBestRoute)]
+    pub fn get_best_route(&self, scoring: JsValue) -> Result<JsValue, JsValue> {
+        let scorer = parse_scorer(&scoring).map_err(js_error)?;
+        let route = self.planner.best_route(scorer.as_ref()).map_err(js_error)?;
+        to_js(&route, "route")
+    }
+
+    /// Like `getTopRoutes` with the planner's settings
+    #[wasm_bindgen(js_name = getTopRoutes)]
+    pub fn get_top_routes(&self, scoring: JsValue, k: usize) -> Result<JsValue, JsValue> {
+        let scorer = parse_scorer(&scoring).map_err(js_error)?;
+        let routes = self
+            .planner
+            .top_routes(scorer.as_ref(), k)
+            .map_err(js_error)?;
+        to_js(&routes, "routes")
+    }
+}
+
+/// Biome ids and exit ids like `"toxicsewers-ramparts"`, the same as the ids of the paths
+fn parse_blacklist(blacklist: &Array) -> Result<Blacklist, Error> {
+    let entries: Vec<String> = blacklist
+        .to_vec()
+        .iter()
+        .map(|element| {
+            JsValue::into_serde::<String>(element)
+                .map_err(|err| Error::InvalidInput(format!("Failed to parse blacklist: {}", err)))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Blacklist::from_entries(entries.iter().map(String::as_str)))
+}
+
+fn parse_route(route: &Array) -> Result<Vec<Id>, Error> {
+    route
+        .to_vec()
+        .iter()
+        .map(|element| {
+            JsValue::into_serde::<Id>(element)
+                .map_err(|err| Error::InvalidInput(format!("Failed to parse route: {}", err)))
+        })
+        .collect()
+}
+
+/// A whole number from 0 to 5
+fn parse_boss_cells(boss_cells: &JsValue) -> Result<u8, Error> {
+    match boss_cells.as_f64() {
+        Some(n) if n.fract() == 0. && (0. ..=5.).contains(&n) => Ok(n as u8),
+        _ => Err(Error::InvalidInput(format!(
+            "Boss cells should be a whole number from 0 to 5, not {:?}",
+            boss_cells
+        ))),
+    }
+}
+
+/// A dataset can be passed either as a biomes.json string or as an already parsed array of biomes,
+/// `undefined` and `null` means the compiled-in biomes.json
+fn parse_dataset(dataset: &JsValue) -> Result<Option<Vec<Biome>>, Error> {
+    if dataset.is_undefined() || dataset.is_null() {
+        return Ok(None);
+    }
+    let biomes = match dataset.as_string() {
+        Some(json) => core::get_biomes_from_str(json.as_str())?,
+        None => JsValue::into_serde::<Vec<Biome>>(dataset)
+            .map_err(|err| Error::InvalidDataset(format!("Failed to parse dataset: {}", err)))?,
+    };
+    Ok(Some(biomes))
+}
+
+/// Runes and keys are passed as `{runes: ["ram"], keys: ["gardener"]}`, boss cells always come from
+/// their own argument
+fn parse_progress(progress: &JsValue, boss_cells: u8) -> Result<Progress, Error> {
+    if progress.is_undefined() || progress.is_null() {
+        return Ok(boss_cells.into());
+    }
+    let progress: Progress = JsValue::into_serde(progress)
+        .map_err(|err| Error::InvalidInput(format!("Failed to parse progress: {}", err)))?;
+    Ok(Progress {
+        boss_cells,
+        ..progress
+    })
+}
+
+/// Constraints are passed as `{start: "promenade", end: "observatory", required_biomes: ["ossuary"],
+/// required_exits: [{from, to}], forbidden_exits: [{from, to}]}`, every field can be left out
+fn parse_constraints(constraints: &JsValue) -> Result<Constraints, Error> {
+    if constraints.is_undefined() || constraints.is_null() {
+        return Ok(Constraints::default());
+    }
+    JsValue::into_serde(constraints)
+        .map_err(|err| Error::InvalidInput(format!("Failed to parse constraints: {}", err)))
+}
+
+/// Scoring is either a boolean saying whether dual scrolls count, or weights like
+/// `{dual_power_scrolls: 0.5, cursed_chest_chance: -0.01}`, `undefined` counts scrolls
+fn parse_scorer(scoring: &JsValue) -> Result<Box<dyn RouteScorer>, Error> {
+    if scoring.is_undefined() || scoring.is_null() {
+        return Ok(Box::new(ScrollScorer::default()));
+    }
+    if let Some(include_dual_scrolls) = scoring.as_bool() {
+        return Ok(Box::new(ScrollScorer {
+            include_dual_scrolls,
+        }));
+    }
+    let scorer: WeightedScorer = JsValue::into_serde(scoring)
+        .map_err(|err| Error::InvalidInput(format!("Failed to parse scoring weights: {}", err)))?;
+    Ok(Box::new(scorer))
+}
+
+/// Errors reach JS as `{code, message}` objects, see `Error`
+fn js_error(error: Error) -> JsValue {
+    JsValue::from_serde(&error).unwrap_or_else(|_| JsValue::from(error.message()))
+}
+
+fn to_js(value: &impl Serialize, what: &str) -> Result<JsValue, JsValue> {
+    JsValue::from_serde(value).map_err(|err| {
+        js_error(Error::Internal(format!(
+            "Failed to serialize {}: {}",
+            what, err
+        )))
+    })
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn get_biomes_should_work_without_options() {
+        let result = get_biomes(
+            Array::new(),
+            JsValue::from(0),
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn get_biomes_should_reject_a_negative_amount_of_boss_cells() {
+        let result = get_biomes(
+            Array::new(),
+            JsValue::from(-1),
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED,
+        );
+        assert!(result.is_err());
+    }
+}
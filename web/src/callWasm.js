@@ -1,9 +1,65 @@
 const imp = import("../pkg/index.js");
 
-export const getBiomes = async function load(blacklist, bossCells) {
+export const getBiomes = async function load(blacklist, bossCells, dataset, progress, constraints, scoring) {
     const {getBiomes} = await imp
         .catch(console.error);
 
     // console.log("call_wasm.get_biomes", blacklist);
-    return getBiomes(blacklist, bossCells);
+    return getBiomes(blacklist, bossCells, dataset, progress, constraints, scoring);
+};
+
+export const getTopRoutes = async function load(blacklist, bossCells, scoring, k, dataset, progress, constraints) {
+    const {getTopRoutes} = await imp
+        .catch(console.error);
+
+    return getTopRoutes(blacklist, bossCells, scoring, k, dataset, progress, constraints);
+};
+
+export const getBestRoute = async function load(blacklist, bossCells, scoring, dataset, progress, constraints) {
+    const {getBestRoute} = await imp
+        .catch(console.error);
+
+    return getBestRoute(blacklist, bossCells, scoring, dataset, progress, constraints);
+};
+
+export const getParetoRoutes = async function load(blacklist, bossCells, objectives, dataset, progress, constraints) {
+    const {getParetoRoutes} = await imp
+        .catch(console.error);
+
+    return getParetoRoutes(blacklist, bossCells, objectives, dataset, progress, constraints);
+};
+
+export const simulateRoute = async function load(route, bossCells, includeDualScrolls, runs, seed, dataset) {
+    const {simulateRoute} = await imp
+        .catch(console.error);
+
+    return simulateRoute(route, bossCells, includeDualScrolls, runs, seed, dataset);
+};
+
+export const explainRoute = async function load(route, bossCells, dataset) {
+    const {explainRoute} = await imp
+        .catch(console.error);
+
+    return explainRoute(route, bossCells, dataset);
+};
+
+export const diffRoutes = async function load(first, second, bossCells, dataset) {
+    const {diffRoutes} = await imp
+        .catch(console.error);
+
+    return diffRoutes(first, second, bossCells, dataset);
+};
+
+export const getMinimumBossCells = async function load(blacklist, dataset, progress, constraints) {
+    const {getMinimumBossCells} = await imp
+        .catch(console.error);
+
+    return getMinimumBossCells(blacklist, dataset, progress, constraints);
+};
+
+export const createPlanner = async function load(dataset) {
+    const {Planner} = await imp
+        .catch(console.error);
+
+    return new Planner(dataset);
 };
@@ -57,7 +57,7 @@ module.exports = {
 			{ from: 'public', to: '.' },
 		]),
 		new WasmPackPlugin({
-			crateDirectory: "../rust/",
+			crateDirectory: "../rust/wasm/",
 			outDir: "../web/pkg",
 		}),
 	],